@@ -0,0 +1,45 @@
+// Copyright 2018 Sergey Sherkunov <leinlawun@leinlawun.org>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(plugin)]
+#![plugin(sig)]
+#![sig]
+
+extern crate sig_runtime;
+
+use sig_runtime::bind;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+pub struct Model {
+    #[sig(notify)]
+    value: i32,
+}
+
+pub struct View {
+    #[sig(notify)]
+    text: i32,
+}
+
+fn main() {
+    let model = Rc::new(RefCell::new(Model::new(1)));
+    let view = Rc::new(RefCell::new(View::new(0)));
+
+    bind(&model, Model::value_property(), &view, View::text_property())
+        .unwrap();
+
+    println!("The view shows {}", view.borrow().text());
+
+    model.borrow_mut().set_value(2);
+
+    println!("The view shows {}", view.borrow().text());
+
+    view.borrow_mut().set_text(3);
+
+    println!("The model holds {}", model.borrow().value());
+}
@@ -0,0 +1,35 @@
+// Copyright 2018 Sergey Sherkunov <leinlawun@leinlawun.org>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(plugin)]
+#![plugin(sig)]
+#![sig]
+
+extern crate sig_runtime;
+
+#[derive(Debug)]
+pub struct Counter {
+    #[sig(notify)]
+    value: u32,
+}
+
+fn changed(old: &u32, new: &u32) {
+    println!("Changed from {} to {}", old, new);
+}
+
+fn main() {
+    let mut counter = Counter::new(0);
+
+    counter.connect_value_changed(Box::new(changed));
+
+    counter.set_value(1);
+    counter.set_value(1);
+    counter.set_value(2);
+
+    println!("{:?} has the value {}", counter, counter.value());
+}
@@ -0,0 +1,51 @@
+// Copyright 2018 Sergey Sherkunov <leinlawun@leinlawun.org>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(plugin)]
+#![plugin(sig)]
+#![sig]
+
+extern crate sig_runtime;
+
+pub struct Document;
+
+impl Document {
+    #[sig(fallible)]
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        println!("Saved to {}", path);
+
+        Ok(())
+    }
+
+    #[sig(fallible = "collect")]
+    pub fn close(&self) -> Result<(), Vec<String>> {
+        println!("Closed");
+
+        Ok(())
+    }
+}
+
+fn writable(path: &str) -> Result<(), String> {
+    if path.starts_with("/readonly/") {
+        Err(format!("{} is read-only", path))
+    } else {
+        Ok(())
+    }
+}
+
+fn main() {
+    let mut document = Document::default();
+
+    document.connect_save(Box::new(writable));
+    document.connect_close(Box::new(|| Err("Unsaved changes".to_owned())));
+    document.connect_close(Box::new(|| Err("Pending upload".to_owned())));
+
+    println!("{:?}", document.save("/tmp/document.txt"));
+    println!("{:?}", document.save("/readonly/document.txt"));
+    println!("{:?}", document.close());
+}
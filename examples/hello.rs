@@ -10,15 +10,13 @@
 #![plugin(sig)]
 #![sig]
 
+extern crate sig_runtime;
+
+use sig_runtime::{DynSignals, HasSignals};
 use std::cell::RefCell;
 use std::rc::Rc;
 
-macro_rules! connect {
-    ($object: expr, $signal: ident, $slot: expr) => {
-        $object.$signal.push(Rc::new($slot))
-    };
-}
-
+#[derive(Clone, Debug, PartialEq)]
 struct Hello {
     value: u32,
 }
@@ -29,11 +27,15 @@ impl Hello {
         println!("Hello, ");
     }
 
-    #[sig]
+    #[sig(dynamic, emit = "pub")]
     pub fn hello_person(&self, name: &str) {
         println!("Hello, ");
     }
 
+    sig! {
+        pub fn answered(&self, value: u32);
+    }
+
     pub fn hello_galaxy(&self) {
         println!("Galaxy!");
     }
@@ -44,6 +46,8 @@ impl Hello {
         self.value += 5;
 
         println!("answer after: {}", self.value);
+
+        self.answered(self.value);
     }
 }
 
@@ -59,31 +63,50 @@ fn person(name: &str) {
     println!("{}", name);
 }
 
+fn answered(value: u32) {
+    println!("The answer is {}", value);
+}
+
 fn main() {
-    let mut hello1 = Hello {
-        hello: vec![],
-        hello_person: vec![],
-        value: 0,
-    };
-    let hello2 = Hello {
-        hello: vec![],
-        hello_person: vec![],
-        value: 0,
-    };
-    let hello3 = Rc::new(RefCell::new(Hello {
-        hello: vec![],
-        hello_person: vec![],
-        value: 0,
-    }));
-
-    connect!(hello1, hello, world);
-    connect!(hello1, hello, move || hello2.hello_galaxy());
-    connect!(hello1, hello, move || {
-        hello3.borrow_mut().answer()
-    });
-    connect!(hello1, hello, universe);
-    connect!(hello1, hello_person, person);
+    let mut hello1 = Hello::new(0);
+    let hello2 = Hello::new(0);
+    let hello3 = Rc::new(RefCell::new(Hello::new(0)));
+
+    hello3.borrow_mut().connect_answered(Box::new(answered));
+
+    hello1.connect_hello(Box::new(world));
+    hello1.connect_hello(Box::new(move || hello2.hello_galaxy()));
+    hello1.connect_hello(Box::new(move || hello3.borrow_mut().answer()));
+    hello1.connect_hello(Box::new(universe));
+    hello1.connect_hello_person(Box::new(person));
+
+    hello1
+        .connect_dyn(
+            "hello_person",
+            Box::new(|args| {
+                if let Some(name) = args[0].downcast_ref::<String>() {
+                    println!("{} from a script", name);
+                }
+            }),
+        )
+        .unwrap();
 
     hello1.hello();
     hello1.hello_person("Number 5");
+    hello1.emit_dyn("hello_person", &[&"Bob"]).unwrap();
+
+    if let Err(error) = hello1.emit_dyn("hello_person", &[&5]) {
+        println!("{}", error);
+    }
+
+    println!("{:?}", hello1.clone());
+
+    for signal in hello1.signals() {
+        println!(
+            "{}({}) has {} connections",
+            signal.name(),
+            signal.args().join(", "),
+            signal.connections(),
+        );
+    }
 }
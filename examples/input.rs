@@ -0,0 +1,51 @@
+// Copyright 2018 Sergey Sherkunov <leinlawun@leinlawun.org>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(plugin)]
+#![plugin(sig)]
+#![sig]
+
+extern crate sig_runtime;
+
+use sig_runtime::Propagation;
+
+pub struct TextField;
+
+impl TextField {
+    #[sig(cancellable)]
+    pub fn key_pressed(&self, key: char) {
+        println!("Typed {:?}", key);
+    }
+}
+
+fn digits_only(key: char) -> Propagation {
+    if key.is_digit(10) {
+        Propagation::Continue
+    } else {
+        println!("Rejected {:?}", key);
+
+        Propagation::Stop
+    }
+}
+
+fn log(key: char) -> Propagation {
+    println!("Pressed {:?}", key);
+
+    Propagation::Continue
+}
+
+fn main() {
+    let mut field = TextField::default();
+
+    field.connect_key_pressed(Box::new(digits_only));
+    field.connect_key_pressed(Box::new(log));
+
+    field.key_pressed('4');
+    field.key_pressed('x');
+    field.key_pressed('2');
+}
@@ -0,0 +1,46 @@
+// Copyright 2018 Sergey Sherkunov <leinlawun@leinlawun.org>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(plugin)]
+#![plugin(sig)]
+#![sig]
+
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde_json;
+extern crate sig_runtime;
+
+#[derive(Serialize, Deserialize, Debug)]
+struct Model {
+    name: String,
+    value: u32,
+}
+
+impl Model {
+    #[sig]
+    pub fn saved(&self, json: &str) {}
+}
+
+fn saved(json: &str) {
+    println!("Saved: {}", json);
+}
+
+fn main() {
+    let mut model = Model::new("answer".to_string(), 42);
+
+    model.connect_saved(Box::new(saved));
+
+    let json = serde_json::to_string(&model).unwrap();
+
+    model.saved(&json);
+
+    let model: Model = serde_json::from_str(&json).unwrap();
+
+    println!("Loaded: {:?}", model);
+}
@@ -0,0 +1,46 @@
+// Copyright 2018 Sergey Sherkunov <leinlawun@leinlawun.org>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(plugin)]
+#![plugin(sig)]
+#![sig]
+
+extern crate sig_runtime;
+
+pub struct Person {
+    #[sig(notify)]
+    first: String,
+    #[sig(notify)]
+    last: String,
+}
+
+impl Person {
+    #[sig(computed(first, last))]
+    pub fn full_name(&self) -> String {
+        println!("Computing the full name...");
+
+        format!("{} {}", self.first, self.last)
+    }
+}
+
+fn renamed(name: &String) {
+    println!("Renamed to {}", name);
+}
+
+fn main() {
+    let mut person = Person::new("John".to_string(), "Smith".to_string());
+
+    println!("{}", person.full_name());
+    println!("{}", person.full_name());
+
+    person.connect_full_name_changed(Box::new(renamed));
+
+    person.set_first("Jane".to_string());
+    person.set_last("Smith".to_string());
+    person.set_last("Doe".to_string());
+}
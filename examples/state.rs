@@ -0,0 +1,58 @@
+// Copyright 2018 Sergey Sherkunov <leinlawun@leinlawun.org>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(plugin)]
+#![plugin(sig)]
+#![sig]
+
+extern crate sig_runtime;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+enum State {
+    Idle,
+    Running,
+    Stopped,
+}
+
+impl State {
+    #[sig]
+    pub fn entered(&self, from: State) {
+        println!("{:?} -> {:?}", from, self);
+    }
+
+    pub fn next(self) -> State {
+        let next = match self {
+            State::Idle => State::Running,
+            State::Running => State::Stopped,
+            State::Stopped => State::Idle,
+        };
+
+        next.entered(self);
+
+        next
+    }
+}
+
+fn running(from: State) {
+    println!("Started running after {:?}", from);
+}
+
+fn stopped(from: State) {
+    println!("Stopped after {:?}", from);
+}
+
+fn main() {
+    State::Running.connect_entered(Box::new(running));
+    State::Stopped.connect_entered(Box::new(stopped));
+
+    let mut state = State::Idle;
+
+    for _ in 0..3 {
+        state = state.next();
+    }
+}
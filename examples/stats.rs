@@ -0,0 +1,39 @@
+// Copyright 2018 Sergey Sherkunov <leinlawun@leinlawun.org>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(plugin)]
+#![plugin(sig)]
+#![sig]
+
+extern crate sig_runtime;
+
+use std::cell::RefCell;
+
+pub struct Sensor;
+
+impl Sensor {
+    #[sig(mutable)]
+    pub fn measured(&self, value: f64) {}
+}
+
+fn main() {
+    let mut sensor = Sensor::default();
+    let mut count = 0;
+    let mut sum = 0.0;
+
+    sensor.connect_measured(Box::new(RefCell::new(move |value| {
+        count += 1;
+        sum += value;
+
+        println!("Average of {} values: {}", count, sum / count as f64);
+    })));
+
+    sensor.measured(1.5);
+    sensor.measured(2.5);
+    sensor.measured(4.0);
+}
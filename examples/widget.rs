@@ -0,0 +1,45 @@
+// Copyright 2018 Sergey Sherkunov <leinlawun@leinlawun.org>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(plugin)]
+#![plugin(sig)]
+#![sig]
+
+extern crate sig_runtime;
+
+trait Widget {
+    #[sig]
+    fn clicked(&self, x: i32, y: i32);
+
+    #[sig]
+    fn resized(&self, width: u32, height: u32) {
+        println!("Resizing...");
+    }
+}
+
+struct Button;
+
+impl Widget for Button {}
+
+fn clicked(x: i32, y: i32) {
+    println!("Clicked at ({}, {})", x, y);
+}
+
+fn resized(width: u32, height: u32) {
+    println!("Resized to {}x{}", width, height);
+}
+
+fn main() {
+    let mut button: Box<Widget> = Box::new(Button::default());
+
+    button.connect_clicked(Box::new(clicked));
+    button.connect_resized(Box::new(resized));
+
+    button.clicked(10, 20);
+    button.resized(640, 480);
+}
@@ -0,0 +1,102 @@
+// Copyright 2018 Sergey Sherkunov <leinlawun@leinlawun.org>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![allow(unknown_lints, bare_trait_objects)]
+
+#[macro_use]
+extern crate criterion;
+extern crate sig_runtime;
+
+use criterion::{black_box, Criterion, Fun};
+use sig_runtime::{Disconnect, Emit, Share, Slots};
+use std::rc::Rc;
+
+type Slot = Fn(u64);
+
+fn slot(value: u64) {
+    black_box(value);
+}
+
+fn shared(count: usize) -> Slots<Slot, Share> {
+    let mut slots = Slots::new();
+
+    for _ in 0..count {
+        slots.connect(Rc::new(slot) as Rc<Slot>);
+    }
+
+    slots
+}
+
+fn boxed(count: usize) -> Slots<Slot, Disconnect> {
+    let mut slots = Slots::new();
+
+    for _ in 0..count {
+        slots.connect(Box::new(slot) as Box<Slot>);
+    }
+
+    slots
+}
+
+fn connect(c: &mut Criterion) {
+    c.bench_function("connect shared", |b| b.iter(|| shared(1)));
+    c.bench_function("connect boxed", |b| b.iter(|| boxed(1)));
+    c.bench_function("connect boxed 8", |b| b.iter(|| boxed(8)));
+}
+
+fn emit(c: &mut Criterion) {
+    c.bench_function_over_inputs(
+        "emit shared",
+        |b, &count| {
+            let slots = shared(count);
+
+            b.iter(|| slots.emit("signal", "Bench", None, |slot| slot(1)))
+        },
+        vec![0, 1, 8],
+    );
+    c.bench_function_over_inputs(
+        "emit boxed",
+        |b, &count| {
+            let slots = boxed(count);
+
+            b.iter(|| slots.emit("signal", "Bench", None, |slot| slot(1)))
+        },
+        vec![0, 1, 8],
+    );
+}
+
+fn method(value: u64) {
+    black_box(value);
+}
+
+fn empty(c: &mut Criterion) {
+    let shared = shared(0);
+    let boxed = boxed(0);
+
+    c.bench_functions(
+        "without slots",
+        vec![
+            Fun::new("method", |b, &value| b.iter(|| method(value))),
+            Fun::new("method and emit shared", move |b, &value| {
+                b.iter(|| {
+                    method(value);
+                    shared.emit("signal", "Bench", None, |slot| slot(value))
+                })
+            }),
+            Fun::new("method and emit boxed", move |b, &value| {
+                b.iter(|| {
+                    method(value);
+                    boxed.emit("signal", "Bench", None, |slot| slot(value))
+                })
+            }),
+        ],
+        1,
+    );
+}
+
+criterion_group!(benches, connect, emit, empty);
+criterion_main!(benches);
@@ -0,0 +1,244 @@
+// Copyright 2018 Sergey Sherkunov <leinlawun@leinlawun.org>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::cell::{Cell, RefCell};
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::rc::{Rc, Weak};
+
+type Slot<T> = Box<Fn(&T, &T)>;
+
+/// The accessors of a property, as returned by `<field>_property()`.
+pub struct Property<O, T> {
+    get: fn(&O) -> &T,
+    set: fn(&mut O, T),
+    connect: fn(&mut O, Slot<T>),
+}
+
+impl<O, T> Property<O, T> {
+    pub fn new(
+        get: fn(&O) -> &T,
+        set: fn(&mut O, T),
+        connect: fn(&mut O, Slot<T>),
+    ) -> Self {
+        Property { get, set, connect }
+    }
+}
+
+impl<O, T> Clone for Property<O, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<O, T> Copy for Property<O, T> {}
+
+/// Marks a binding as busy while it forwards a change, so that the change
+/// coming back from the other side isn't forwarded again.
+struct Busy<'a>(&'a Cell<bool>);
+
+impl<'a> Busy<'a> {
+    fn enter(busy: &'a Cell<bool>) -> Option<Self> {
+        if busy.replace(true) {
+            None
+        } else {
+            Some(Busy(busy))
+        }
+    }
+}
+
+impl<'a> Drop for Busy<'a> {
+    fn drop(&mut self) {
+        self.0.set(false)
+    }
+}
+
+fn forward<O, T>(
+    busy: &Rc<Cell<bool>>,
+    target: &Rc<RefCell<O>>,
+    property: Property<O, T>,
+) -> Slot<T>
+where
+    O: 'static,
+    T: Clone + 'static,
+{
+    let busy = busy.clone();
+    let target = Rc::downgrade(target);
+
+    Box::new(move |_: &T, new: &T| {
+        if let (Some(_busy), Some(target)) =
+            (Busy::enter(&busy), Weak::upgrade(&target))
+        {
+            (property.set)(&mut target.borrow_mut(), new.clone());
+        }
+    })
+}
+
+/// The reasons two properties can't be bound.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BindError {
+    /// Both properties belong to the same object, which is still borrowed by
+    /// the setter of one of them when the change is forwarded to the other.
+    SameObject,
+}
+
+impl Display for BindError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            BindError::SameObject => {
+                write!(f, "the properties belong to the same object")
+            },
+        }
+    }
+}
+
+impl Error for BindError {
+    fn description(&self) -> &str {
+        "the properties can't be bound"
+    }
+}
+
+/// Keeps two properties in sync, starting with the value of the first one.
+///
+/// A change of either property is forwarded to the other one, but not back
+/// again. The objects are only weakly referenced by the binding, so it ends
+/// when either of them is dropped.
+pub fn bind<A, B, T>(
+    a: &Rc<RefCell<A>>,
+    a_property: Property<A, T>,
+    b: &Rc<RefCell<B>>,
+    b_property: Property<B, T>,
+) -> Result<(), BindError>
+where
+    A: 'static,
+    B: 'static,
+    T: Clone + 'static,
+{
+    if a.as_ptr() as *const () == b.as_ptr() as *const () {
+        return Err(BindError::SameObject);
+    }
+
+    let busy = Rc::new(Cell::new(false));
+    let value = (a_property.get)(&a.borrow()).clone();
+
+    (b_property.set)(&mut b.borrow_mut(), value);
+    (a_property.connect)(&mut a.borrow_mut(), forward(&busy, b, b_property));
+    (b_property.connect)(&mut b.borrow_mut(), forward(&busy, a, a_property));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::mem;
+
+    #[derive(Default)]
+    struct Model {
+        value: i32,
+        sets: usize,
+        slots: Vec<Slot<i32>>,
+    }
+
+    impl Model {
+        fn value(&self) -> &i32 {
+            &self.value
+        }
+
+        fn set_value(&mut self, value: i32) {
+            self.sets += 1;
+
+            if self.value != value {
+                let old = mem::replace(&mut self.value, value);
+
+                for slot in &self.slots {
+                    slot(&old, &self.value);
+                }
+            }
+        }
+
+        fn connect_value_changed(&mut self, slot: Slot<i32>) {
+            self.slots.push(slot)
+        }
+
+        fn value_property() -> Property<Self, i32> {
+            Property::new(
+                Model::value,
+                Model::set_value,
+                Model::connect_value_changed,
+            )
+        }
+    }
+
+    fn model(value: i32) -> Rc<RefCell<Model>> {
+        Rc::new(RefCell::new(Model {
+            value,
+            ..Model::default()
+        }))
+    }
+
+    #[test]
+    fn initial() {
+        let (a, b) = (model(1), model(2));
+
+        bind(&a, Model::value_property(), &b, Model::value_property())
+            .unwrap();
+
+        assert_eq!(b.borrow().value, 1);
+        assert_eq!(a.borrow().sets, 0);
+    }
+
+    #[test]
+    fn two_way() {
+        let (a, b) = (model(1), model(1));
+
+        bind(&a, Model::value_property(), &b, Model::value_property())
+            .unwrap();
+        a.borrow_mut().set_value(2);
+
+        assert_eq!(b.borrow().value, 2);
+        assert_eq!((a.borrow().sets, b.borrow().sets), (1, 2));
+
+        b.borrow_mut().set_value(3);
+
+        assert_eq!(a.borrow().value, 3);
+        assert_eq!((a.borrow().sets, b.borrow().sets), (2, 3));
+    }
+
+    #[test]
+    fn dropped() {
+        let (a, b) = (model(1), model(1));
+
+        bind(&a, Model::value_property(), &b, Model::value_property())
+            .unwrap();
+
+        let weak = Rc::downgrade(&b);
+
+        drop(b);
+        a.borrow_mut().set_value(2);
+
+        assert!(weak.upgrade().is_none());
+        assert_eq!(a.borrow().value, 2);
+        assert_eq!(a.borrow().sets, 1);
+    }
+
+    #[test]
+    fn same_object() {
+        let a = model(1);
+
+        assert_eq!(
+            bind(&a, Model::value_property(), &a, Model::value_property()),
+            Err(BindError::SameObject),
+        );
+
+        a.borrow_mut().set_value(2);
+
+        assert!(a.borrow().slots.is_empty());
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright 2018 Sergey Sherkunov <leinlawun@leinlawun.org>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::fmt::{Debug, Formatter, Result};
+use std::hash::{Hash, Hasher};
+
+/// The cached value of a computed property.
+///
+/// Like the signal storage, the cache compares equal to any other cache and
+/// hashes to nothing, and a clone starts with an empty cache.
+pub struct Computed<T> {
+    value: RefCell<Option<T>>,
+}
+
+impl<T> Computed<T> {
+    pub fn new() -> Self {
+        Computed {
+            value: RefCell::new(None),
+        }
+    }
+
+    /// Forgets the cached value and returns it.
+    pub fn invalidate(&self) -> Option<T> {
+        self.value.borrow_mut().take()
+    }
+}
+
+impl<T: Clone> Computed<T> {
+    /// Returns the cached value, computing it first if there is none.
+    pub fn get<F: FnOnce() -> T>(&self, compute: F) -> T {
+        if let Some(ref value) = *self.value.borrow() {
+            return value.clone();
+        }
+
+        let value = compute();
+
+        *self.value.borrow_mut() = Some(value.clone());
+
+        value
+    }
+}
+
+impl<T> Default for Computed<T> {
+    fn default() -> Self {
+        Computed::new()
+    }
+}
+
+impl<T> Clone for Computed<T> {
+    fn clone(&self) -> Self {
+        Computed::new()
+    }
+}
+
+impl<T: Debug> Debug for Computed<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        f.debug_struct("Computed")
+            .field("value", &*self.value.borrow())
+            .finish()
+    }
+}
+
+impl<T> PartialEq for Computed<T> {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl<T> Eq for Computed<T> {}
+
+impl<T> PartialOrd for Computed<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Computed<T> {
+    fn cmp(&self, _other: &Self) -> Ordering {
+        Ordering::Equal
+    }
+}
+
+impl<T> Hash for Computed<T> {
+    fn hash<H: Hasher>(&self, _state: &mut H) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get() {
+        let computed = Computed::new();
+
+        assert_eq!(computed.get(|| 1), 1);
+        assert_eq!(computed.get(|| 2), 1);
+    }
+
+    #[test]
+    fn invalidate() {
+        let computed = Computed::new();
+
+        assert_eq!(computed.invalidate(), None);
+
+        computed.get(|| 1);
+
+        assert_eq!(computed.invalidate(), Some(1));
+        assert_eq!(computed.get(|| 2), 2);
+    }
+
+    #[test]
+    fn clone() {
+        let computed = Computed::new();
+
+        computed.get(|| 1);
+
+        assert_eq!(computed.clone().invalidate(), None);
+        assert_eq!(computed.invalidate(), Some(1));
+    }
+}
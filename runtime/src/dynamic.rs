@@ -0,0 +1,197 @@
+// Copyright 2018 Sergey Sherkunov <leinlawun@leinlawun.org>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::any::Any;
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// A slot that receives the arguments of a signal without knowing their
+/// types.
+pub type DynSlot = Box<Fn(&[&Any])>;
+
+/// The reasons a signal can't be connected to or emitted by name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DynError {
+    /// The type has no signal with this name.
+    UnknownSignal(String),
+    /// The signal isn't declared with `#[sig(dynamic)]`.
+    NotDynamic(&'static str),
+    /// The signal can't be connected to or emitted outside of its module,
+    /// since its `connect_<signal>` or `emit_<signal>` method isn't `pub`.
+    Private(&'static str),
+    /// The signal takes a different number of arguments.
+    ArgumentCount {
+        signal: &'static str,
+        expected: usize,
+        found: usize,
+    },
+    /// The signal takes an argument of a different type.
+    ArgumentType {
+        signal: &'static str,
+        index: usize,
+        expected: &'static str,
+    },
+}
+
+impl Display for DynError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            DynError::UnknownSignal(ref signal) => {
+                write!(f, "there is no signal named `{}`", signal)
+            },
+            DynError::NotDynamic(signal) => {
+                write!(f, "the signal `{}` isn't dynamic", signal)
+            },
+            DynError::Private(signal) => {
+                write!(f, "the signal `{}` isn't public", signal)
+            },
+            DynError::ArgumentCount {
+                signal,
+                expected,
+                found,
+            } => write!(
+                f,
+                "the signal `{}` takes {} arguments, but {} were given",
+                signal, expected, found,
+            ),
+            DynError::ArgumentType {
+                signal,
+                index,
+                expected,
+            } => write!(
+                f,
+                "the argument {} of the signal `{}` must be `{}`",
+                index, signal, expected,
+            ),
+        }
+    }
+}
+
+impl Error for DynError {
+    fn description(&self) -> &str {
+        "the signal can't be used by name"
+    }
+}
+
+/// Implemented for every type with dynamic signals, so that its signals can be
+/// used by name.
+///
+/// The slots see an argument of a reference type as the value behind the
+/// reference, and an argument of type `&str` as a `String`. An argument taken
+/// by value is cloned out of the `&Any` it is passed as, so its type must
+/// be `Clone`.
+///
+/// Only the signals whose `connect_<signal>` and `emit_<signal>` methods are
+/// `pub` can be connected to and emitted by name.
+pub trait DynSignals {
+    fn connect_dyn(
+        &mut self,
+        signal: &str,
+        slot: DynSlot,
+    ) -> Result<(), DynError>;
+
+    fn emit_dyn(&self, signal: &str, args: &[&Any]) -> Result<(), DynError>;
+}
+
+#[doc(hidden)]
+pub fn dyn_args(
+    signal: &'static str,
+    args: &[&Any],
+    expected: usize,
+) -> Result<(), DynError> {
+    if args.len() == expected {
+        Ok(())
+    } else {
+        Err(DynError::ArgumentCount {
+            signal,
+            expected,
+            found: args.len(),
+        })
+    }
+}
+
+#[doc(hidden)]
+pub fn dyn_arg<'a, T: Any>(
+    signal: &'static str,
+    args: &[&'a Any],
+    index: usize,
+    expected: &'static str,
+) -> Result<&'a T, DynError> {
+    args[index]
+        .downcast_ref::<T>()
+        .ok_or(DynError::ArgumentType {
+            signal,
+            index,
+            expected,
+        })
+}
+
+#[doc(hidden)]
+pub fn dyn_str<'a>(
+    signal: &'static str,
+    args: &[&'a Any],
+    index: usize,
+) -> Result<&'a str, DynError> {
+    let arg = args[index];
+
+    arg.downcast_ref::<&'static str>()
+        .cloned()
+        .or_else(|| arg.downcast_ref::<String>().map(String::as_str))
+        .ok_or(DynError::ArgumentType {
+            signal,
+            index,
+            expected: "&str",
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn args() {
+        assert_eq!(dyn_args("clicked", &[&1], 1), Ok(()));
+        assert_eq!(
+            dyn_args("clicked", &[&1, &2], 1),
+            Err(DynError::ArgumentCount {
+                signal: "clicked",
+                expected: 1,
+                found: 2,
+            }),
+        );
+    }
+
+    #[test]
+    fn arg() {
+        assert_eq!(dyn_arg::<i32>("clicked", &[&1], 0, "i32"), Ok(&1));
+        assert_eq!(
+            dyn_arg::<i32>("clicked", &[&1u8], 0, "i32"),
+            Err(DynError::ArgumentType {
+                signal: "clicked",
+                index: 0,
+                expected: "i32",
+            }),
+        );
+    }
+
+    #[test]
+    fn str() {
+        let owned = "Bob".to_owned();
+
+        assert_eq!(dyn_str("hello", &[&"Bob"], 0), Ok("Bob"));
+        assert_eq!(dyn_str("hello", &[&owned], 0), Ok("Bob"));
+        assert_eq!(
+            dyn_str("hello", &[&1], 0),
+            Err(DynError::ArgumentType {
+                signal: "hello",
+                index: 0,
+                expected: "&str",
+            }),
+        );
+    }
+}
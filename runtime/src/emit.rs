@@ -0,0 +1,494 @@
+// Copyright 2018 Sergey Sherkunov <leinlawun@leinlawun.org>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::any::Any;
+use std::cell::{Cell, RefCell, RefMut};
+use std::collections::VecDeque;
+use std::fmt::{Debug, Formatter, Result};
+use std::mem::replace;
+use std::ops::Deref;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::result::Result as StdResult;
+
+#[cfg(feature = "trace")]
+use trace::{trace, Emission};
+
+#[cfg(not(feature = "trace"))]
+struct Emission;
+
+#[cfg(not(feature = "trace"))]
+impl Emission {
+    fn new(_signal: &'static str, _sender: &str, _slots: usize) -> Self {
+        Emission
+    }
+}
+
+/// What an emission does when one of its slots panics.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PanicPolicy {
+    /// The panic unwinds through the emission, so the remaining slots aren't
+    /// called.
+    Propagate,
+    /// The panic is caught and collected, and the remaining slots are called.
+    Continue,
+    /// Like `Continue`, but the slot is also disconnected.
+    Disconnect,
+}
+
+/// Whether the slots of a cancellable signal after the current one are
+/// called.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Propagation {
+    Continue,
+    /// The remaining slots aren't called, and the method of the signal
+    /// doesn't run its body.
+    Stop,
+}
+
+/// A panic caught while calling a slot.
+pub struct SlotPanic {
+    signal: &'static str,
+    index: usize,
+    payload: Box<Any + Send>,
+}
+
+impl SlotPanic {
+    pub fn signal(&self) -> &'static str {
+        self.signal
+    }
+
+    /// The position of the slot among the connected ones.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The message of the panic, if it has one.
+    pub fn message(&self) -> Option<&str> {
+        self.payload
+            .downcast_ref::<&str>()
+            .cloned()
+            .or_else(|| {
+                self.payload.downcast_ref::<String>().map(String::as_str)
+            })
+    }
+
+    pub fn into_payload(self) -> Box<Any + Send> {
+        self.payload
+    }
+}
+
+impl Debug for SlotPanic {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        f.debug_struct("SlotPanic")
+            .field("signal", &self.signal)
+            .field("index", &self.index)
+            .field("message", &self.message())
+            .finish()
+    }
+}
+
+/// The number of caught panics kept on a thread until they are taken.
+pub const MAX_PANICS: usize = 64;
+
+thread_local! {
+    static POLICY: Cell<Option<PanicPolicy>> = Cell::default();
+    static PANICS: RefCell<VecDeque<SlotPanic>> = RefCell::default();
+}
+
+/// Sets the panic policy of the signals on the current thread that aren't
+/// declared with a policy of their own.
+pub fn set_panic_policy(policy: PanicPolicy) {
+    POLICY.with(|current| current.set(Some(policy)))
+}
+
+/// Returns the panics caught on the current thread since the last call.
+///
+/// Only the last `MAX_PANICS` panics are kept, so a thread that emits signals
+/// with the `Continue` or `Disconnect` policy should take them regularly to
+/// learn about all of them.
+pub fn take_panics() -> Vec<SlotPanic> {
+    let panics = PANICS.with(|panics| {
+        replace(&mut *panics.borrow_mut(), VecDeque::with_capacity(MAX_PANICS))
+    });
+
+    panics.into_iter().collect()
+}
+
+#[cfg(feature = "trace")]
+fn call_slot<F, G>(
+    emission: &Emission,
+    index: usize,
+    slot: &F,
+    call: &mut G,
+) -> bool
+where
+    F: ?Sized,
+    G: FnMut(&F) -> bool,
+{
+    trace(emission, index, || call(slot))
+}
+
+#[cfg(not(feature = "trace"))]
+fn call_slot<F, G>(
+    _emission: &Emission,
+    _index: usize,
+    slot: &F,
+    call: &mut G,
+) -> bool
+where
+    F: ?Sized,
+    G: FnMut(&F) -> bool,
+{
+    call(slot)
+}
+
+/// Borrows a mutable slot of the signal `signal` to call it.
+///
+/// # Panics
+///
+/// When the slot is already running, e.g. because it emitted its own signal.
+/// The panic is handled by the panic policy of the signal like any other.
+#[doc(hidden)]
+pub fn borrow_slot<'a, F: ?Sized>(
+    signal: &'static str,
+    slot: &'a RefCell<F>,
+) -> RefMut<'a, F> {
+    slot.try_borrow_mut().unwrap_or_else(|_| {
+        panic!(
+            "a slot of the signal `{}` was called while it was already \
+             running",
+            signal
+        )
+    })
+}
+
+/// Emitting a signal to its connected slots.
+///
+/// Every method handles the panics of the slots as `policy` says, or as the
+/// policy of the current thread says if there is none.
+pub trait Emit<F: ?Sized> {
+    /// Calls `call` with every slot until it returns `false`, and returns
+    /// whether every slot was called.
+    fn emit_while<G: FnMut(&F) -> bool>(
+        &self,
+        signal: &'static str,
+        sender: &'static str,
+        policy: Option<PanicPolicy>,
+        call: G,
+    ) -> bool;
+
+    /// Calls `call` with every slot.
+    #[inline]
+    fn emit<G: FnMut(&F)>(
+        &self,
+        signal: &'static str,
+        sender: &'static str,
+        policy: Option<PanicPolicy>,
+        mut call: G,
+    ) {
+        self.emit_while(signal, sender, policy, |slot| {
+            call(slot);
+
+            true
+        });
+    }
+
+    /// Calls `call` with every slot until one of them stops the propagation,
+    /// and returns whether one did.
+    #[inline]
+    fn cancellable_emit<G: FnMut(&F) -> Propagation>(
+        &self,
+        signal: &'static str,
+        sender: &'static str,
+        policy: Option<PanicPolicy>,
+        mut call: G,
+    ) -> Propagation {
+        if self.emit_while(signal, sender, policy, |slot| {
+            call(slot) == Propagation::Continue
+        }) {
+            Propagation::Continue
+        } else {
+            Propagation::Stop
+        }
+    }
+
+    /// Calls `call` with every slot until one of them fails, and returns
+    /// its error.
+    #[inline]
+    fn try_emit<E, G: FnMut(&F) -> StdResult<(), E>>(
+        &self,
+        signal: &'static str,
+        sender: &'static str,
+        policy: Option<PanicPolicy>,
+        mut call: G,
+    ) -> StdResult<(), E> {
+        let mut error = None;
+
+        self.emit_while(signal, sender, policy, |slot| match call(slot) {
+            Ok(()) => true,
+            Err(slot_error) => {
+                error = Some(slot_error);
+
+                false
+            },
+        });
+
+        error.map_or(Ok(()), Err)
+    }
+
+    /// Calls `call` with every slot, and returns the errors of all the slots
+    /// that failed.
+    #[inline]
+    fn collect_emit<E, G: FnMut(&F) -> StdResult<(), E>>(
+        &self,
+        signal: &'static str,
+        sender: &'static str,
+        policy: Option<PanicPolicy>,
+        mut call: G,
+    ) -> StdResult<(), Vec<E>> {
+        let mut errors = vec![];
+
+        self.emit_while(signal, sender, policy, |slot| {
+            if let Err(error) = call(slot) {
+                errors.push(error);
+            }
+
+            true
+        });
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Calls `call` with every slot of `slots` but the ones at the indices in
+/// `skip` until it returns `false`.
+///
+/// Returns whether every slot was called, and the ascending indices of the
+/// slots that have to be disconnected.
+pub(crate) fn emit<F, P, G>(
+    signal: &'static str,
+    sender: &'static str,
+    policy: Option<PanicPolicy>,
+    slots: &[P],
+    skip: &[usize],
+    mut call: G,
+) -> (bool, Vec<usize>)
+where
+    F: ?Sized,
+    P: Deref<Target = F>,
+    G: FnMut(&F) -> bool,
+{
+    let policy = policy
+        .or_else(|| POLICY.with(Cell::get))
+        .unwrap_or(PanicPolicy::Propagate);
+    let emission = Emission::new(signal, sender, slots.len() - skip.len());
+    let mut disconnected = vec![];
+
+    for (index, slot) in slots.iter().enumerate() {
+        if !skip.is_empty() && skip.contains(&index) {
+            continue;
+        }
+
+        if policy == PanicPolicy::Propagate {
+            if !call_slot(&emission, index, &**slot, &mut call) {
+                return (false, disconnected);
+            }
+        } else {
+            match catch_unwind(AssertUnwindSafe(|| {
+                call_slot(&emission, index, &**slot, &mut call)
+            })) {
+                Ok(true) => {},
+                Ok(false) => return (false, disconnected),
+                Err(payload) => {
+                    PANICS.with(|panics| {
+                        let mut panics = panics.borrow_mut();
+
+                        if panics.len() == MAX_PANICS {
+                            panics.pop_front();
+                        }
+
+                        panics.push_back(SlotPanic {
+                            signal,
+                            index,
+                            payload,
+                        })
+                    });
+
+                    if policy == PanicPolicy::Disconnect {
+                        disconnected.push(index);
+                    }
+                },
+            }
+        }
+    }
+
+    (true, disconnected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::rc::Rc;
+
+    use storage::Slots;
+
+    fn slots(calls: &Rc<Cell<usize>>) -> Slots<Fn(bool)> {
+        let mut slots = Slots::<Fn(bool)>::new();
+
+        for _ in 0..3 {
+            let calls = calls.clone();
+
+            slots.connect(Rc::new(move |_| calls.set(calls.get() + 1)));
+        }
+
+        slots.connect(Rc::new(|fail| {
+            if fail {
+                panic!("failed")
+            }
+        }));
+
+        slots
+    }
+
+    fn emit(slots: &Slots<Fn(bool)>, policy: PanicPolicy) {
+        slots.emit("changed", "Model", Some(policy), |slot| slot(true))
+    }
+
+    #[test]
+    fn continue_policy() {
+        let calls = Rc::new(Cell::new(0));
+        let slots = slots(&calls);
+
+        take_panics();
+        emit(&slots, PanicPolicy::Continue);
+        emit(&slots, PanicPolicy::Continue);
+
+        let panics = take_panics();
+
+        assert_eq!(calls.get(), 6);
+        assert_eq!(slots.len(), 4);
+        assert_eq!(panics.len(), 2);
+        assert_eq!(panics[0].signal(), "changed");
+        assert_eq!(panics[0].index(), 3);
+        assert_eq!(panics[0].message(), Some("failed"));
+    }
+
+    #[test]
+    fn disconnect_policy() {
+        let calls = Rc::new(Cell::new(0));
+        let slots = slots(&calls);
+
+        take_panics();
+        emit(&slots, PanicPolicy::Disconnect);
+
+        assert_eq!(slots.len(), 3);
+        assert_eq!(take_panics().len(), 1);
+
+        emit(&slots, PanicPolicy::Disconnect);
+
+        assert_eq!(calls.get(), 6);
+        assert!(take_panics().is_empty());
+    }
+
+    #[test]
+    fn max_panics() {
+        let calls = Rc::new(Cell::new(0));
+        let slots = slots(&calls);
+
+        take_panics();
+
+        for _ in 0..MAX_PANICS + 1 {
+            emit(&slots, PanicPolicy::Continue);
+        }
+
+        assert_eq!(take_panics().len(), MAX_PANICS);
+        assert!(take_panics().is_empty());
+    }
+
+    type Fallible = Fn(u32) -> StdResult<(), u32>;
+
+    fn fallible(calls: &Rc<Cell<usize>>) -> Slots<Fallible> {
+        let mut slots = Slots::<Fallible>::new();
+
+        for index in 0..4 {
+            let calls = calls.clone();
+
+            slots.connect(Rc::new(move |value| {
+                calls.set(calls.get() + 1);
+
+                if index % 2 == 1 {
+                    Err(value + index)
+                } else {
+                    Ok(())
+                }
+            }));
+        }
+
+        slots
+    }
+
+    #[test]
+    fn try_emit() {
+        let calls = Rc::new(Cell::new(0));
+        let slots = fallible(&calls);
+
+        assert_eq!(
+            slots.try_emit("saved", "Model", None, |slot| slot(10)),
+            Err(11),
+        );
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn collect_emit() {
+        let calls = Rc::new(Cell::new(0));
+        let slots = fallible(&calls);
+
+        assert_eq!(
+            slots.collect_emit("saved", "Model", None, |slot| slot(10)),
+            Err(vec![11, 13]),
+        );
+        assert_eq!(calls.get(), 4);
+    }
+
+    #[test]
+    fn cancellable_emit() {
+        let calls = Rc::new(Cell::new(0));
+        let mut slots = Slots::<Fn(bool) -> Propagation>::new();
+
+        for index in 0..3 {
+            let calls = calls.clone();
+
+            slots.connect(Rc::new(move |stop| {
+                calls.set(calls.get() + 1);
+
+                if stop && index == 1 {
+                    Propagation::Stop
+                } else {
+                    Propagation::Continue
+                }
+            }));
+        }
+
+        assert_eq!(
+            slots.cancellable_emit("closed", "Model", None, |slot| slot(true)),
+            Propagation::Stop,
+        );
+        assert_eq!(calls.get(), 2);
+        assert_eq!(
+            slots.cancellable_emit("closed", "Model", None, |slot| slot(false)),
+            Propagation::Continue,
+        );
+        assert_eq!(calls.get(), 5);
+    }
+}
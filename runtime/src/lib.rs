@@ -0,0 +1,43 @@
+// Copyright 2018 Sergey Sherkunov <leinlawun@leinlawun.org>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Runtime support for the code generated by the `sig` plugin.
+
+#![allow(unknown_lints, bare_trait_objects)]
+
+#[cfg(all(feature = "trace", feature = "log"))]
+#[macro_use]
+extern crate log;
+
+mod binding;
+mod computed;
+mod dynamic;
+mod emit;
+mod list;
+mod meta;
+mod storage;
+mod table;
+#[cfg(feature = "trace")]
+mod trace;
+
+pub use binding::{bind, BindError, Property};
+pub use computed::Computed;
+pub use dynamic::{
+    dyn_arg, dyn_args, dyn_str, DynError, DynSignals, DynSlot,
+};
+pub use emit::{
+    borrow_slot, set_panic_policy, take_panics, Emit, PanicPolicy,
+    Propagation, SlotPanic, MAX_PANICS,
+};
+pub use meta::{HasSignals, SignalMeta};
+pub use storage::{ClonePolicy, Disconnect, Share, Slots};
+pub use table::{connect, disconnect, keyed_slots, slots, KeyedSlots};
+#[cfg(all(feature = "trace", feature = "log"))]
+pub use trace::LogTracer;
+#[cfg(feature = "trace")]
+pub use trace::{clear_tracer, set_tracer, Emission, Tracer};
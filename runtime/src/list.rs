@@ -0,0 +1,146 @@
+// Copyright 2018 Sergey Sherkunov <leinlawun@leinlawun.org>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::mem;
+use std::ops::Deref;
+
+/// A list of slots that keeps a single slot inline, since most signals have
+/// at most one connection.
+pub(crate) enum SlotList<T> {
+    One([T; 1]),
+    Many(Vec<T>),
+}
+
+impl<T> SlotList<T> {
+    pub(crate) fn new() -> Self {
+        SlotList::Many(vec![])
+    }
+
+    pub(crate) fn push(&mut self, slot: T) {
+        match mem::replace(self, SlotList::new()) {
+            SlotList::Many(ref slots) if slots.is_empty() => {
+                *self = SlotList::One([slot])
+            },
+            SlotList::One([first]) => *self = SlotList::Many(vec![first, slot]),
+            SlotList::Many(mut slots) => {
+                slots.push(slot);
+
+                *self = SlotList::Many(slots)
+            },
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        *self = SlotList::new()
+    }
+
+    /// Removes the slots at the ascending `indices`.
+    pub(crate) fn remove(&mut self, indices: &[usize]) {
+        match *self {
+            SlotList::One(_) => {
+                if indices.contains(&0) {
+                    self.clear()
+                }
+            },
+            SlotList::Many(ref mut slots) => {
+                for &index in indices.iter().rev() {
+                    slots.remove(index);
+                }
+            },
+        }
+    }
+}
+
+impl<T> Deref for SlotList<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        match *self {
+            SlotList::One(ref slot) => slot,
+            SlotList::Many(ref slots) => slots,
+        }
+    }
+}
+
+impl<T> From<Vec<T>> for SlotList<T> {
+    fn from(mut slots: Vec<T>) -> Self {
+        if slots.len() == 1 {
+            SlotList::One([slots.remove(0)])
+        } else {
+            SlotList::Many(slots)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(slots: Vec<u32>) -> SlotList<u32> {
+        SlotList::from(slots)
+    }
+
+    fn one(slots: &SlotList<u32>) -> Option<u32> {
+        match *slots {
+            SlotList::One([slot]) => Some(slot),
+            SlotList::Many(_) => None,
+        }
+    }
+
+    fn many(slots: &SlotList<u32>) -> Option<&[u32]> {
+        match *slots {
+            SlotList::One(_) => None,
+            SlotList::Many(ref slots) => Some(slots),
+        }
+    }
+
+    #[test]
+    fn push() {
+        let mut slots = SlotList::new();
+
+        slots.push(1);
+        assert_eq!(one(&slots), Some(1));
+
+        slots.push(2);
+        assert_eq!(many(&slots), Some(&[1, 2][..]));
+    }
+
+    #[test]
+    fn from() {
+        assert_eq!(many(&list(vec![])), Some(&[][..]));
+        assert_eq!(one(&list(vec![1])), Some(1));
+        assert_eq!(many(&list(vec![1, 2])), Some(&[1, 2][..]));
+    }
+
+    #[test]
+    fn remove() {
+        let mut slots = list(vec![1]);
+
+        slots.remove(&[1]);
+        assert_eq!(one(&slots), Some(1));
+
+        slots.remove(&[0]);
+        assert!(slots.is_empty());
+
+        let mut slots = list(vec![1, 2, 3, 4]);
+
+        slots.remove(&[0, 2]);
+        assert_eq!(&*slots, &[2, 4]);
+    }
+
+    #[test]
+    fn clear() {
+        let mut slots = list(vec![1, 2]);
+
+        slots.clear();
+        assert!(slots.is_empty());
+
+        slots.push(3);
+        assert_eq!(one(&slots), Some(3));
+    }
+}
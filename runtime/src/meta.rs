@@ -0,0 +1,48 @@
+// Copyright 2018 Sergey Sherkunov <leinlawun@leinlawun.org>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/// Describes a signal and its current connections.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignalMeta {
+    name: &'static str,
+    args: &'static [&'static str],
+    connections: usize,
+}
+
+impl SignalMeta {
+    pub fn new(
+        name: &'static str,
+        args: &'static [&'static str],
+        connections: usize,
+    ) -> Self {
+        SignalMeta {
+            name,
+            args,
+            connections,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The argument types as written in the signal declaration.
+    pub fn args(&self) -> &'static [&'static str] {
+        self.args
+    }
+
+    pub fn connections(&self) -> usize {
+        self.connections
+    }
+}
+
+/// Implemented for every type with signals.
+pub trait HasSignals {
+    /// Lists the signals of the value with their current connections.
+    fn signals(&self) -> Vec<SignalMeta>;
+}
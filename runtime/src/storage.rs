@@ -0,0 +1,182 @@
+// Copyright 2018 Sergey Sherkunov <leinlawun@leinlawun.org>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::fmt::{Debug, Formatter, Result};
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::rc::Rc;
+
+use emit::{emit, Emit, PanicPolicy};
+use list::SlotList;
+
+/// Decides what cloning a structure does with the connections of a signal.
+///
+/// Closures can't be copied, so a clone either shares the connected slots
+/// with the original or starts without any. Only shared slots need to be
+/// reference counted, so the policy also decides how the slots are stored.
+/// The signals of a structure that doesn't derive `Clone` are never cloned,
+/// so they are stored with `Disconnect`.
+pub trait ClonePolicy<F: ?Sized> {
+    type Slot: Deref<Target = F>;
+
+    fn clone_slots(slots: &[Self::Slot]) -> Vec<Self::Slot>;
+}
+
+/// The clone is connected to the same slots as the original, which are
+/// stored in an `Rc`.
+pub struct Share;
+
+impl<F: ?Sized> ClonePolicy<F> for Share {
+    type Slot = Rc<F>;
+
+    fn clone_slots(slots: &[Rc<F>]) -> Vec<Rc<F>> {
+        slots.to_vec()
+    }
+}
+
+/// The clone starts without any connected slots, which are stored in a
+/// `Box`.
+pub struct Disconnect;
+
+impl<F: ?Sized> ClonePolicy<F> for Disconnect {
+    type Slot = Box<F>;
+
+    fn clone_slots(_slots: &[Box<F>]) -> Vec<Box<F>> {
+        vec![]
+    }
+}
+
+/// The slots connected to a signal.
+///
+/// The storage is invisible to the derives of the structure it is injected
+/// into: it compares equal to any other storage, hashes to nothing and is
+/// debug-printed as its slot count.
+///
+/// Emitting a signal without slots only checks that there are none.
+pub struct Slots<F: ?Sized, C: ClonePolicy<F> = Share> {
+    slots: SlotList<C::Slot>,
+    /// The ascending indices of the slots disconnected by an emission, which
+    /// are skipped until the slots can be changed.
+    disconnected: RefCell<Vec<usize>>,
+    policy: PhantomData<C>,
+}
+
+impl<F: ?Sized, C: ClonePolicy<F>> Slots<F, C> {
+    pub fn new() -> Self {
+        Slots {
+            slots: SlotList::new(),
+            disconnected: RefCell::default(),
+            policy: PhantomData,
+        }
+    }
+
+    pub fn connect(&mut self, slot: C::Slot) {
+        let disconnected = self.disconnected.get_mut();
+
+        self.slots.remove(disconnected);
+        self.slots.push(slot);
+        disconnected.clear()
+    }
+
+    pub fn disconnect_all(&mut self) {
+        self.disconnected.get_mut().clear();
+        self.slots.clear()
+    }
+
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.disconnected.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<F: ?Sized, C: ClonePolicy<F>> Emit<F> for Slots<F, C> {
+    #[inline]
+    fn emit_while<G: FnMut(&F) -> bool>(
+        &self,
+        signal: &'static str,
+        sender: &'static str,
+        policy: Option<PanicPolicy>,
+        call: G,
+    ) -> bool {
+        if self.slots.is_empty() {
+            return true;
+        }
+
+        let skip = self.disconnected.borrow().clone();
+        let (completed, disconnected) =
+            emit(signal, sender, policy, &self.slots, &skip, call);
+
+        if !disconnected.is_empty() {
+            let mut pending = self.disconnected.borrow_mut();
+
+            pending.extend(disconnected);
+            pending.sort();
+            pending.dedup();
+        }
+
+        completed
+    }
+}
+
+impl<F: ?Sized, C: ClonePolicy<F>> Default for Slots<F, C> {
+    fn default() -> Self {
+        Slots::new()
+    }
+}
+
+impl<F: ?Sized, C: ClonePolicy<F>> Clone for Slots<F, C> {
+    fn clone(&self) -> Self {
+        let mut slots = SlotList::from(C::clone_slots(&self.slots));
+
+        if slots.len() == self.slots.len() {
+            slots.remove(&self.disconnected.borrow());
+        }
+
+        Slots {
+            slots,
+            disconnected: RefCell::default(),
+            policy: PhantomData,
+        }
+    }
+}
+
+impl<F: ?Sized, C: ClonePolicy<F>> Debug for Slots<F, C> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        f.debug_struct("Slots").field("len", &self.len()).finish()
+    }
+}
+
+impl<F: ?Sized, C: ClonePolicy<F>> PartialEq for Slots<F, C> {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl<F: ?Sized, C: ClonePolicy<F>> Eq for Slots<F, C> {}
+
+impl<F: ?Sized, C: ClonePolicy<F>> PartialOrd for Slots<F, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<F: ?Sized, C: ClonePolicy<F>> Ord for Slots<F, C> {
+    fn cmp(&self, _other: &Self) -> Ordering {
+        Ordering::Equal
+    }
+}
+
+impl<F: ?Sized, C: ClonePolicy<F>> Hash for Slots<F, C> {
+    fn hash<H: Hasher>(&self, _state: &mut H) {}
+}
@@ -0,0 +1,293 @@
+// Copyright 2018 Sergey Sherkunov <leinlawun@leinlawun.org>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+use emit::{self, Emit, PanicPolicy};
+
+type Tables = HashMap<(TypeId, &'static str), Box<Any>>;
+
+/// The slots of the values of a type connected to one of its signals.
+///
+/// The slots of a value are shared with the emissions in progress, so an
+/// emission doesn't copy them and a slot connected during an emission copies
+/// them only then.
+type Table<K, F> = HashMap<K, Rc<Vec<Rc<F>>>>;
+
+thread_local! {
+    static TABLES: RefCell<Tables> = RefCell::new(HashMap::new());
+}
+
+fn with_table<K, F, R, W>(signal: &'static str, with: W) -> Option<R>
+where
+    K: Eq + Hash + 'static,
+    F: ?Sized + 'static,
+    W: FnOnce(&mut Table<K, F>) -> R,
+{
+    TABLES.with(|tables| {
+        tables
+            .borrow_mut()
+            .get_mut(&(TypeId::of::<K>(), signal))
+            .map(|table| {
+                with(table.downcast_mut().expect("signal table type mismatch"))
+            })
+    })
+}
+
+/// Connects `slot` to the signal `signal` of every value equal to `key`.
+///
+/// Values are keys, not instances, so their slots aren't disconnected when a
+/// value is dropped: they stay in the table of the current thread until they
+/// are disconnected with `disconnect` or by their panic policy.
+pub fn connect<K, F>(signal: &'static str, key: &K, slot: Rc<F>)
+where
+    K: Clone + Eq + Hash + 'static,
+    F: ?Sized + 'static,
+{
+    TABLES.with(|tables| {
+        let mut tables = tables.borrow_mut();
+        let table = tables
+            .entry((TypeId::of::<K>(), signal))
+            .or_insert_with(|| Box::new(Table::<K, F>::new()))
+            .downcast_mut::<Table<K, F>>()
+            .expect("signal table type mismatch");
+        let slots = table
+            .entry(key.clone())
+            .or_insert_with(|| Rc::new(Vec::new()));
+
+        Rc::make_mut(slots).push(slot)
+    })
+}
+
+/// Returns the slots connected to the signal `signal` of `key`.
+///
+/// The slots are copied out of the table, so a slot may connect new slots
+/// while the signal is being emitted.
+pub fn slots<K, F>(signal: &'static str, key: &K) -> Vec<Rc<F>>
+where
+    K: Eq + Hash + 'static,
+    F: ?Sized + 'static,
+{
+    shared_slots(signal, key).map_or_else(Vec::new, |slots| slots.to_vec())
+}
+
+fn shared_slots<K, F>(signal: &'static str, key: &K) -> Option<Rc<Vec<Rc<F>>>>
+where
+    K: Eq + Hash + 'static,
+    F: ?Sized + 'static,
+{
+    with_table(signal, |table: &mut Table<K, F>| table.get(key).cloned())
+        .and_then(|slots| slots)
+}
+
+/// Disconnects every slot from the signal `signal` of `key`.
+pub fn disconnect<K, F>(signal: &'static str, key: &K)
+where
+    K: Eq + Hash + 'static,
+    F: ?Sized + 'static,
+{
+    with_table(signal, |table: &mut Table<K, F>| {
+        table.remove(key);
+    });
+}
+
+/// The slots connected to the signal `signal` of `key`.
+pub struct KeyedSlots<'a, K: 'a, F: ?Sized> {
+    signal: &'static str,
+    key: &'a K,
+    slots: PhantomData<Rc<F>>,
+}
+
+/// Returns the slots connected to the signal `signal` of `key`, to emit the
+/// signal with.
+pub fn keyed_slots<'a, K, F>(
+    signal: &'static str,
+    key: &'a K,
+) -> KeyedSlots<'a, K, F>
+where
+    K: Eq + Hash + 'static,
+    F: ?Sized + 'static,
+{
+    KeyedSlots {
+        signal,
+        key,
+        slots: PhantomData,
+    }
+}
+
+impl<'a, K, F> Emit<F> for KeyedSlots<'a, K, F>
+where
+    K: Eq + Hash + 'static,
+    F: ?Sized + 'static,
+{
+    fn emit_while<G: FnMut(&F) -> bool>(
+        &self,
+        signal: &'static str,
+        sender: &'static str,
+        policy: Option<PanicPolicy>,
+        call: G,
+    ) -> bool {
+        let slots = match shared_slots::<K, F>(self.signal, self.key) {
+            Some(slots) => slots,
+            None => return true,
+        };
+        let (completed, disconnected) =
+            emit::emit(signal, sender, policy, &slots[..], &[], call);
+
+        if !disconnected.is_empty() {
+            with_table(self.signal, |table: &mut Table<K, F>| {
+                let mut empty = false;
+
+                if let Some(connected) = table.get_mut(self.key) {
+                    let connected = Rc::make_mut(connected);
+
+                    connected.retain(|slot| {
+                        !disconnected
+                            .iter()
+                            .any(|&index| Rc::ptr_eq(slot, &slots[index]))
+                    });
+                    empty = connected.is_empty();
+                }
+
+                if empty {
+                    table.remove(self.key);
+                }
+            });
+        }
+
+        completed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::cell::Cell;
+
+    use emit::take_panics;
+
+    fn tables() -> usize {
+        TABLES.with(|tables| tables.borrow().len())
+    }
+
+    fn count(calls: &Rc<Cell<usize>>) -> Rc<Fn(bool)> {
+        let calls = calls.clone();
+
+        Rc::new(move |_| calls.set(calls.get() + 1))
+    }
+
+    fn emit(key: &u32, policy: Option<PanicPolicy>) {
+        keyed_slots::<u32, Fn(bool)>("changed", key).emit(
+            "changed",
+            "u32",
+            policy,
+            |slot| slot(true),
+        )
+    }
+
+    #[test]
+    fn separate_keys() {
+        let (first, second) = (Rc::new(Cell::new(0)), Rc::new(Cell::new(0)));
+
+        connect("changed", &1u32, count(&first));
+        connect("changed", &2u32, count(&second));
+        connect("changed", &2u32, count(&second));
+        emit(&1, None);
+
+        assert_eq!((first.get(), second.get()), (1, 0));
+
+        emit(&2, None);
+
+        assert_eq!((first.get(), second.get()), (1, 2));
+        assert_eq!(slots::<u32, Fn(bool)>("changed", &1).len(), 1);
+        assert_eq!(slots::<u32, Fn(bool)>("changed", &2).len(), 2);
+    }
+
+    #[test]
+    fn disconnect_key() {
+        let calls = Rc::new(Cell::new(0));
+
+        connect("changed", &1u32, count(&calls));
+        connect("changed", &2u32, count(&calls));
+        disconnect::<u32, Fn(bool)>("changed", &1);
+        emit(&1, None);
+        emit(&2, None);
+
+        assert_eq!(calls.get(), 1);
+        assert!(slots::<u32, Fn(bool)>("changed", &1).is_empty());
+        assert_eq!(slots::<u32, Fn(bool)>("changed", &2).len(), 1);
+    }
+
+    #[test]
+    fn disconnect_policy() {
+        let calls = Rc::new(Cell::new(0));
+
+        for &key in &[1u32, 2] {
+            connect("changed", &key, count(&calls));
+            connect(
+                "changed",
+                &key,
+                Rc::new(|fail| {
+                    if fail {
+                        panic!("failed")
+                    }
+                }) as Rc<Fn(bool)>,
+            );
+            connect("changed", &key, count(&calls));
+        }
+
+        take_panics();
+        emit(&1, Some(PanicPolicy::Disconnect));
+
+        let panics = take_panics();
+
+        assert_eq!(calls.get(), 2);
+        assert_eq!(panics.len(), 1);
+        assert_eq!(panics[0].index(), 1);
+        assert_eq!(slots::<u32, Fn(bool)>("changed", &1).len(), 2);
+        assert_eq!(slots::<u32, Fn(bool)>("changed", &2).len(), 3);
+
+        emit(&1, Some(PanicPolicy::Disconnect));
+
+        assert_eq!(calls.get(), 4);
+        assert!(take_panics().is_empty());
+    }
+
+    #[test]
+    fn emit_without_slots() {
+        keyed_slots::<u32, Fn()>("clicked", &1).emit(
+            "clicked",
+            "u32",
+            None,
+            |slot| slot(),
+        );
+        disconnect::<u32, Fn()>("clicked", &1);
+
+        assert_eq!(tables(), 0);
+
+        connect::<u32, Fn()>("clicked", &1, Rc::new(|| {}));
+        keyed_slots::<u32, Fn()>("clicked", &2).emit(
+            "clicked",
+            "u32",
+            None,
+            |slot| slot(),
+        );
+
+        assert_eq!(tables(), 1);
+        assert_eq!(
+            with_table("clicked", |table: &mut Table<u32, Fn()>| table.len()),
+            Some(1),
+        );
+    }
+}
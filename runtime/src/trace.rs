@@ -0,0 +1,107 @@
+// Copyright 2018 Sergey Sherkunov <leinlawun@leinlawun.org>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+/// An emission of a signal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Emission {
+    signal: &'static str,
+    sender: &'static str,
+    slots: usize,
+}
+
+impl Emission {
+    pub fn new(
+        signal: &'static str,
+        sender: &'static str,
+        slots: usize,
+    ) -> Self {
+        Emission {
+            signal,
+            sender,
+            slots,
+        }
+    }
+
+    pub fn signal(&self) -> &'static str {
+        self.signal
+    }
+
+    /// The type that declares the signal.
+    pub fn sender(&self) -> &'static str {
+        self.sender
+    }
+
+    /// The number of slots called by the emission.
+    pub fn slots(&self) -> usize {
+        self.slots
+    }
+}
+
+/// Receives a report for every slot called by an emission.
+pub trait Tracer {
+    fn slot(&self, emission: &Emission, index: usize, duration: Duration);
+}
+
+impl<F: Fn(&Emission, usize, Duration)> Tracer for F {
+    fn slot(&self, emission: &Emission, index: usize, duration: Duration) {
+        self(emission, index, duration)
+    }
+}
+
+/// Logs every slot call at the trace level.
+#[cfg(feature = "log")]
+pub struct LogTracer;
+
+#[cfg(feature = "log")]
+impl Tracer for LogTracer {
+    fn slot(&self, emission: &Emission, index: usize, duration: Duration) {
+        trace!(
+            "{}::{}: slot {} of {} took {:?}",
+            emission.sender,
+            emission.signal,
+            index + 1,
+            emission.slots,
+            duration,
+        );
+    }
+}
+
+thread_local! {
+    static TRACER: RefCell<Option<Rc<Tracer>>> = RefCell::new(None);
+}
+
+/// Sets the tracer of the emissions on the current thread.
+pub fn set_tracer<T: Tracer + 'static>(tracer: T) {
+    TRACER.with(|current| *current.borrow_mut() = Some(Rc::new(tracer)));
+}
+
+/// Stops tracing the emissions on the current thread.
+pub fn clear_tracer() {
+    TRACER.with(|current| *current.borrow_mut() = None);
+}
+
+pub(crate) fn trace<F, R>(emission: &Emission, index: usize, slot: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    match TRACER.with(|current| current.borrow().clone()) {
+        Some(tracer) => {
+            let start = Instant::now();
+            let result = slot();
+
+            tracer.slot(emission, index, start.elapsed());
+
+            result
+        },
+        None => slot(),
+    }
+}
@@ -13,407 +13,3615 @@ extern crate syntax;
 extern crate syntax_pos;
 
 use rustc_plugin::Registry;
+use std::env;
+use std::fs;
+use std::mem;
+use std::path::PathBuf;
+use std::ptr;
+use syntax::abi::Abi;
 use syntax::ast::{
-    AngleBracketedParameterData, Attribute, Block, ExprKind, FunctionRetTy,
-    ImplItem, ImplItemKind, Item, ItemKind, MetaItem, MethodSig, Mod,
+    Arg, Arm, Attribute, BinOpKind, Block, CaptureBy, Constness, CrateSugar,
+    Defaultness, Expr, ExprKind, Field, FnDecl, FunctionRetTy, GenericParam,
+    Generics, ImplItem, ImplItemKind, ImplPolarity, Item, ItemKind, Lifetime,
+    Mac, Mac_, MetaItem, MethodSig, Mod, MutTy, Mutability,
     ParenthesizedParameterData, PatKind, Path, PathParameters, PathSegment,
-    SpannedIdent, StructField, Ty, TyKind, VariantData, Visibility,
-    VisibilityKind, DUMMY_NODE_ID,
+    SelfKind, SpannedIdent, Stmt, StructField, TraitItem, TraitItemKind,
+    TraitRef, Ty, TyKind, UnOp, Unsafety, UseTree, UseTreeKind, VariantData,
+    Visibility, VisibilityKind, DUMMY_NODE_ID,
 };
+use syntax::codemap::respan;
+use syntax::config::StripUnconfigured;
 use syntax::ext::base::{Annotatable, ExtCtxt, SyntaxExtension};
 use syntax::ext::build::AstBuilder;
-use syntax::ext::quote::rt::Span;
+use syntax::ext::quote::rt::{Span, ToTokens};
+use syntax::fold::{self, Folder};
+use syntax::parse::token::Token;
+use syntax::print::pprust;
 use syntax::ptr::P;
-use syntax::symbol::Symbol;
+use syntax::symbol::{keywords, Symbol};
+use syntax::tokenstream::{TokenStream, TokenTree};
+use syntax::visit::Visitor;
 use syntax_pos::DUMMY_SP;
 use syntax_pos::symbol::Ident;
 
+#[derive(Clone, Copy, PartialEq)]
+enum Storage {
+    Field,
+    Table,
+    Trait,
+}
+
+#[derive(Clone, Default)]
+struct Options {
+    clone: Option<Ident>,
+    connect: Option<VisibilityKind>,
+    emit: Option<VisibilityKind>,
+    notify: bool,
+    computed: Option<Vec<Ident>>,
+    dynamic: bool,
+    panic: Option<Ident>,
+    fallible: Option<Ident>,
+    cancellable: bool,
+    mutable: bool,
+    debug: bool,
+    result: Option<P<Ty>>,
+}
+
+#[derive(Clone)]
+struct Signal {
+    span: Span,
+    ident: Ident,
+    inputs: Vec<Arg>,
+    storage: Storage,
+    options: Options,
+}
+
+struct Error {
+    span: Span,
+    message: &'static str,
+    help: Option<&'static str>,
+    note: Option<(Span, &'static str)>,
+    suggestion: Option<(Span, &'static str, &'static str)>,
+}
+
+impl Error {
+    fn new(span: Span, message: &'static str) -> Self {
+        Error {
+            span,
+            message,
+            help: None,
+            note: None,
+            suggestion: None,
+        }
+    }
+
+    fn help(self, help: &'static str) -> Self {
+        Error {
+            help: Some(help),
+            ..self
+        }
+    }
+
+    fn note(self, span: Span, note: &'static str) -> Self {
+        Error {
+            note: Some((span, note)),
+            ..self
+        }
+    }
+
+    fn suggest(
+        self,
+        span: Span,
+        message: &'static str,
+        suggestion: &'static str,
+    ) -> Self
+    {
+        Error {
+            suggestion: Some((span, message, suggestion)),
+            ..self
+        }
+    }
+}
+
+struct SigSpan(Span);
+
+impl Folder for SigSpan {
+    fn new_span(&mut self, span: Span) -> Span {
+        if span == DUMMY_SP {
+            self.0
+        } else {
+            span
+        }
+    }
+
+    fn fold_mac(&mut self, mac: Mac) -> Mac {
+        fold::noop_fold_mac(mac, self)
+    }
+}
+
+struct SigLifetimes(Vec<Lifetime>);
+
+impl<'a> Visitor<'a> for SigLifetimes {
+    fn visit_lifetime(&mut self, lifetime: &'a Lifetime) {
+        if lifetime.ident.name != keywords::StaticLifetime.name() {
+            self.0.push(*lifetime);
+        }
+    }
+
+    fn visit_mac(&mut self, _mac: &'a Mac) {}
+}
+
+#[derive(Clone)]
+struct Inject {
+    ty: P<Ty>,
+    target: Option<usize>,
+    fields: Vec<StructField>,
+    signals: Vec<Signal>,
+}
+
 fn sig_mod(
     cx: &mut ExtCtxt,
     visited: &[&Mod],
-    injects: &mut [Vec<(P<Ty>, Vec<StructField>)>],
+    injects: &mut [Vec<Inject>],
     item: &Mod,
 ) -> Mod
 {
     let new_visited = [visited, &[item]].concat();
     let visited_ref = new_visited.as_slice();
-    let mut new_injects = [injects, &mut [vec![]]].concat();
+    let mut new_injects = [&mut *injects, &mut [vec![]]].concat();
     let injects_ref = new_injects.as_mut();
+    let mut properties = vec![];
     let module = Mod {
         inner: item.inner,
         items: item.items
             .iter()
-            .map(|item| sig_item(cx, visited_ref, injects_ref, item))
+            .map(|item| {
+                let item = sig_item(cx, visited_ref, injects_ref, item);
+                let (item, property) =
+                    sig_properties(cx, visited_ref, injects_ref, &item);
+
+                properties.extend(property);
+
+                item
+            })
             .collect(),
     };
 
-    injects_ref[injects_ref.len() - 1]
-        .iter()
-        .fold(module, |module, inject| {
-            if let TyKind::Path(_, ref path) = inject.0.node {
-                module
-                    .items
-                    .iter()
-                    .enumerate()
-                    .find(|&(_, item)| {
-                        path == &Path::from_ident(path.span, item.ident)
+    let level = injects_ref.len() - 1;
+    let (local, foreign) =
+        sig_merge(cx, level, mem::replace(&mut injects_ref[level], vec![]));
+
+    match injects.last_mut() {
+        Some(parent) => parent.extend(foreign),
+        None => {
+            let foreign =
+                foreign.iter().filter(|inject| !inject.fields.is_empty());
+
+            for inject in foreign {
+                cx.span_err(
+                    inject.ty.span,
+                    "The structure for which the signals are implemented is \
+                     not found in the module of the implementation or in its \
+                     parents. Please define the structure in one of them.",
+                );
+            }
+        },
+    }
+
+    let injected = local
+        .iter()
+        .filter(|inject| !inject.fields.is_empty())
+        .fold(module.clone(), |module, inject| {
+            module
+                .items
+                .iter()
+                .enumerate()
+                .find(|&(_, item)| sig_local(&inject.ty, item))
+                .map(|(number, item)| {
+                    (
+                        number,
+                        match item.node {
+                            ItemKind::Struct(ref content, ref generics) => {
+                                ItemKind::Struct(
+                                    match content {
+                                        &VariantData::Struct(
+                                            ref fields,
+                                            node_id,
+                                        ) => {
+                                            VariantData::Struct(
+                                                [
+                                                    fields.clone(),
+                                                    sig_inject(
+                                                        cx,
+                                                        item,
+                                                        &inject.fields,
+                                                    ),
+                                                ].concat(),
+                                                node_id,
+                                            )
+                                        },
+                                        &VariantData::Tuple(
+                                            ref fields,
+                                            node_id,
+                                        ) => {
+                                            VariantData::Tuple(
+                                                [
+                                                    fields.clone(),
+                                                    sig_inject(
+                                                        cx,
+                                                        item,
+                                                        &inject.fields,
+                                                    ),
+                                                ].concat(),
+                                                node_id,
+                                            )
+                                        },
+                                        &VariantData::Unit(node_id) => {
+                                            VariantData::Struct(
+                                                sig_inject(
+                                                    cx,
+                                                    item,
+                                                    &inject.fields,
+                                                ),
+                                                node_id,
+                                            )
+                                        },
+                                    },
+                                    generics.clone(),
+                                )
+                            },
+                            ref node => {
+                                cx.span_err(
+                                    item.span,
+                                    "Only structures and tuple structures are \
+                                     supported.",
+                                );
+
+                                node.clone()
+                            },
+                        },
+                    )
+                })
+                .map_or_else(
+                    || module.clone(),
+                    |(number, item_node)| {
+                        let module = module.clone();
+                        let mut items = module.items;
+                        let item = items[number].clone();
+                        let item = P(Item {
+                            ident: item.ident,
+                            attrs: item.attrs.clone(),
+                            id: item.id,
+                            node: item_node,
+                            vis: item.vis.clone(),
+                            span: item.span,
+                            tokens: item.tokens.clone(),
+                        });
+
+                        items[number] = item;
+
+                        Mod {
+                            inner: module.inner,
+                            items,
+                        }
+                    },
+                )
+        });
+    let constructors = module
+        .items
+        .iter()
+        .zip(injected.items.iter())
+        .flat_map(|(item, injected_item)| {
+            sig_constructors(cx, visited_ref, item, injected_item)
+                .into_iter()
+                .flat_map(move |new| SigSpan(item.span).fold_item(new))
+        })
+        .collect::<Vec<_>>();
+    let metas = injected
+        .items
+        .iter()
+        .flat_map(|item| {
+            sig_meta(cx, &local, item)
+                .into_iter()
+                .chain(sig_dyn(cx, &local, item))
+                .flat_map(move |new| SigSpan(item.span).fold_item(new))
+        })
+        .collect::<Vec<_>>();
+
+    let module = Mod {
+        inner: injected.inner,
+        items: [injected.items, constructors, properties, metas].concat(),
+    };
+
+    sig_debug(cx, visited_ref, &local, &module);
+
+    module
+}
+
+fn sig_local(ty: &Ty, item: &Item) -> bool {
+    match item.node {
+        ItemKind::Struct(..) | ItemKind::Enum(..) | ItemKind::Union(..) => {
+            sig_ty_ident(ty).map_or(false, |ty| ty.name == item.ident.name)
+        },
+        _ => false,
+    }
+}
+
+fn sig_merge(
+    cx: &mut ExtCtxt,
+    level: usize,
+    injects: Vec<Inject>,
+) -> (Vec<Inject>, Vec<Inject>)
+{
+    let (mut local, foreign): (Vec<_>, Vec<_>) = injects
+        .into_iter()
+        .partition(|inject| inject.target == Some(level));
+    let mut declared: Vec<(Option<Symbol>, Symbol, Span)> = vec![];
+    let mut errors = vec![];
+
+    for inject in &mut local {
+        let ty = sig_ty_ident(&inject.ty).map(|ty| ty.name);
+        let mut duplicates = vec![];
+
+        for signal in &inject.signals {
+            let first = declared
+                .iter()
+                .find(|&&(declared_ty, name, _)| {
+                    declared_ty == ty && name == signal.ident.name
+                })
+                .map(|&(_, _, span)| span);
+
+            match first {
+                Some(span) => {
+                    errors.push(
+                        Error::new(
+                            signal.span,
+                            "A signal with this name is already declared for \
+                             the type.",
+                        ).note(span, "the signal is first declared here"),
+                    );
+                    duplicates.push(signal.ident.name);
+                },
+                None => declared.push((ty, signal.ident.name, signal.span)),
+            }
+        }
+
+        inject
+            .signals
+            .retain(|signal| !duplicates.contains(&signal.ident.name));
+        inject.fields.retain(|field| {
+            field
+                .ident
+                .map_or(true, |ident| !duplicates.contains(&ident.name))
+        });
+    }
+
+    sig_report(cx, &errors);
+
+    (local, foreign)
+}
+
+fn sig_expansion(module: &Mod, ident: Ident) -> String {
+    module
+        .items
+        .iter()
+        .filter(|item| match item.node {
+            ItemKind::Impl(_, _, _, _, _, ref ty, _) => {
+                sig_ty_ident(ty).map_or(false, |ty| ty.name == ident.name)
+            },
+            _ => item.ident.name == ident.name,
+        })
+        .map(|item| pprust::item_to_string(item))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn sig_module_path(visited: &[&Mod]) -> Vec<Ident> {
+    visited
+        .windows(2)
+        .filter_map(|pair| {
+            pair[0].items.iter().find(|item| match item.node {
+                ItemKind::Mod(ref module) => ptr::eq(module, pair[1]),
+                _ => false,
+            })
+        })
+        .map(|item| item.ident)
+        .collect()
+}
+
+fn sig_debug(
+    cx: &ExtCtxt,
+    visited: &[&Mod],
+    injects: &[Inject],
+    module: &Mod,
+) {
+    let dir = env::var_os("SIG_RS_EXPAND_DIR").map(PathBuf::from);
+    let source = visited[visited.len() - 1];
+    let path = sig_module_path(visited)
+        .iter()
+        .fold(cx.ecfg.crate_name.clone(), |path, module| {
+            format!("{}.{}", path, module.name)
+        });
+
+    for item in &module.items {
+        let signals: Vec<bool> = match item.node {
+            ItemKind::Struct(..) | ItemKind::Enum(..) => injects
+                .iter()
+                .filter(|inject| {
+                    sig_ty_ident(&inject.ty)
+                        .map_or(false, |ty| ty.name == item.ident.name)
+                })
+                .flat_map(|inject| {
+                    inject.signals.iter().map(|signal| signal.options.debug)
+                })
+                .collect(),
+            ItemKind::Trait(..) => sig_traits(source, item.ident)
+                .unwrap_or(&[])
+                .iter()
+                .filter(|item| sig_find(&item.attrs))
+                .map(|item| sig_options(&item.attrs).0.debug)
+                .collect(),
+            _ => vec![],
+        };
+
+        if signals.is_empty() {
+            continue;
+        }
+
+        let expansion = sig_expansion(module, item.ident);
+
+        if signals.iter().any(|&debug| debug) {
+            eprintln!("{}\n", expansion);
+        }
+
+        if let Some(ref dir) = dir {
+            let path = dir.join(format!("{}.{}.rs", path, item.ident.name));
+
+            if fs::create_dir_all(dir)
+                .and_then(|_| fs::write(&path, expansion + "\n"))
+                .is_err()
+            {
+                cx.span_warn(
+                    item.span,
+                    "The expansion can't be written to SIG_RS_EXPAND_DIR.",
+                );
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+fn sig_serde(cx: &ExtCtxt, attrs: &[Attribute]) -> Vec<Attribute> {
+    if sig_derives(attrs, "Serialize") || sig_derives(attrs, "Deserialize") {
+        vec![cx.attribute(
+            DUMMY_SP,
+            cx.meta_list(
+                DUMMY_SP,
+                Symbol::intern("serde"),
+                vec![cx.meta_list_item_word(DUMMY_SP, Symbol::intern("skip"))],
+            ),
+        )]
+    } else {
+        vec![]
+    }
+}
+
+#[cfg(not(feature = "serde"))]
+fn sig_serde(_cx: &ExtCtxt, _attrs: &[Attribute]) -> Vec<Attribute> {
+    vec![]
+}
+
+fn sig_inject(
+    cx: &ExtCtxt,
+    item: &Item,
+    fields: &[StructField],
+) -> Vec<StructField>
+{
+    let attrs = sig_serde(cx, &item.attrs);
+
+    fields
+        .iter()
+        .map(|field| {
+            StructField {
+                attrs: [field.attrs.clone(), attrs.clone()].concat(),
+                ..field.clone()
+            }
+        })
+        .collect()
+}
+
+fn sig_storage(visited: &[&Mod], ty: &P<Ty>) -> Storage {
+    match sig_resolve(visited, ty).map(|(_, item)| &item.node) {
+        Some(&ItemKind::Enum(..)) => Storage::Table,
+        _ => Storage::Field,
+    }
+}
+
+fn sig_derives(attrs: &[Attribute], name: &str) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.check_name("derive"))
+        .filter_map(|attr| attr.meta_item_list())
+        .any(|items| items.iter().any(|item| item.check_name(name)))
+}
+
+fn sig_path(path: &Path) -> bool {
+    path == &Path::from_ident(path.span, Ident::from_str("sig"))
+}
+
+fn sig_is(attr: &Attribute) -> bool {
+    sig_path(&attr.path)
+}
+
+fn sig_find(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(sig_is)
+}
+
+fn sig_strip(attrs: &[Attribute]) -> Vec<Attribute> {
+    attrs.iter().filter(|attr| !sig_is(attr)).cloned().collect()
+}
+
+fn sig_vis(value: &str) -> Option<VisibilityKind> {
+    let value = value.trim();
+
+    if value.is_empty() {
+        Some(VisibilityKind::Inherited)
+    } else if value == "pub" {
+        Some(VisibilityKind::Public)
+    } else if value.starts_with("pub(") && value.ends_with(')') {
+        let restriction = value[4..value.len() - 1].trim();
+        let path = if restriction == "crate" {
+            return Some(VisibilityKind::Crate(CrateSugar::PubCrate));
+        } else if restriction == "self" || restriction == "super" {
+            restriction
+        } else if restriction.starts_with("in ") {
+            restriction[3..].trim()
+        } else {
+            return None;
+        };
+        let segments = path.split("::").map(str::trim).collect::<Vec<_>>();
+
+        if segments.iter().any(|segment| segment.is_empty()) {
+            None
+        } else {
+            Some(VisibilityKind::Restricted {
+                path: P(Path {
+                    span: DUMMY_SP,
+                    segments: segments
+                        .iter()
+                        .map(|segment| {
+                            PathSegment::from_ident(
+                                Ident::from_str(segment),
+                                DUMMY_SP,
+                            )
+                        })
+                        .collect(),
+                }),
+                id: DUMMY_NODE_ID,
+            })
+        }
+    } else {
+        None
+    }
+}
+
+fn sig_options(attrs: &[Attribute]) -> (Options, Vec<Error>) {
+    let mut options = Options::default();
+    let mut errors = vec![];
+
+    for item in attrs
+        .iter()
+        .filter(|attr| sig_is(attr))
+        .filter_map(|attr| attr.meta_item_list())
+        .flat_map(|items| items)
+    {
+        if item.check_name("clone") {
+            options.clone = match item.value_str() {
+                Some(value) if value == "share" => {
+                    Some(Ident::from_str("Share"))
+                },
+                Some(value) if value == "disconnect" => {
+                    Some(Ident::from_str("Disconnect"))
+                },
+                _ => {
+                    errors.push(Error::new(
+                        item.span,
+                        "The clone policy can only be \"share\" or \
+                         \"disconnect\".",
+                    ));
+
+                    None
+                },
+            };
+        } else if item.check_name("panic") {
+            options.panic = match item.value_str() {
+                Some(value) if value == "propagate" => {
+                    Some(Ident::from_str("Propagate"))
+                },
+                Some(value) if value == "continue" => {
+                    Some(Ident::from_str("Continue"))
+                },
+                Some(value) if value == "disconnect" => {
+                    Some(Ident::from_str("Disconnect"))
+                },
+                _ => {
+                    errors.push(Error::new(
+                        item.span,
+                        "The panic policy can only be \"propagate\", \
+                         \"continue\" or \"disconnect\".",
+                    ));
+
+                    None
+                },
+            };
+        } else if item.check_name("fallible") {
+            options.fallible = match item.value_str() {
+                None if item.is_word() => Some(Ident::from_str("try_emit")),
+                Some(value) if value == "short" => {
+                    Some(Ident::from_str("try_emit"))
+                },
+                Some(value) if value == "collect" => {
+                    Some(Ident::from_str("collect_emit"))
+                },
+                _ => {
+                    errors.push(Error::new(
+                        item.span,
+                        "The error policy can only be \"short\" or \
+                         \"collect\".",
+                    ));
+
+                    None
+                },
+            };
+        } else if item.check_name("vis") || item.check_name("emit") {
+            let vis =
+                item.value_str().and_then(|value| sig_vis(&value.as_str()));
+
+            if vis.is_none() {
+                errors.push(Error::new(
+                    item.span,
+                    "The visibility must be written as in the source code, \
+                     e.g. \"pub\" or \"pub(crate)\".",
+                ));
+            }
+
+            if item.check_name("vis") {
+                options.connect = vis;
+            } else {
+                options.emit = vis;
+            }
+        } else if item.check_name("notify") && item.is_word() {
+            options.notify = true;
+        } else if item.check_name("dynamic") && item.is_word() {
+            options.dynamic = true;
+        } else if item.check_name("cancellable") && item.is_word() {
+            options.cancellable = true;
+        } else if item.check_name("mutable") && item.is_word() {
+            options.mutable = true;
+        } else if item.check_name("debug") && item.is_word() {
+            options.debug = true;
+        } else if item.check_name("computed") {
+            options.computed = item.meta_item_list().and_then(|items| {
+                items
+                    .iter()
+                    .map(|item| {
+                        item.word()
+                            .map(|word| Ident::with_empty_ctxt(word.name()))
+                    })
+                    .collect()
+            });
+
+            if options.computed.is_none() {
+                errors.push(Error::new(
+                    item.span,
+                    "The dependencies of a computed property must be written \
+                     as computed(field, ...).",
+                ));
+            }
+        } else {
+            errors.push(
+                Error::new(item.span, "Unknown option of the sig attribute.")
+                    .help(
+                        "the options are clone, vis, emit, notify, computed, \
+                         dynamic, panic, fallible, cancellable, mutable and \
+                         debug",
+                    ),
+            );
+        }
+    }
+
+    (options, errors)
+}
+
+fn sig_errors(
+    span: Span,
+    attrs: &[Attribute],
+    sig: &MethodSig,
+) -> Vec<Error>
+{
+    let (options, mut errors) = sig_options(attrs);
+
+    if options.notify {
+        errors.push(Error::new(
+            span,
+            "Only fields can be declared as properties.",
+        ));
+    }
+
+    if options.fallible.is_some() && options.computed.is_some() {
+        errors.push(Error::new(span, "A computed property can't be fallible."));
+    }
+
+    if options.fallible.is_some() && options.dynamic {
+        errors.push(Error::new(span, "Fallible signals can't be dynamic."));
+    }
+
+    if options.cancellable && options.computed.is_some() {
+        errors.push(Error::new(
+            span,
+            "A computed property can't be cancellable.",
+        ));
+    }
+
+    if options.cancellable && options.fallible.is_some() {
+        errors.push(Error::new(
+            span,
+            "A signal can't be both fallible and cancellable.",
+        ));
+    }
+
+    if options.cancellable && options.dynamic {
+        errors.push(Error::new(span, "Cancellable signals can't be dynamic."));
+    }
+
+    match sig.decl.inputs.get(0) {
+        Some(arg) => {
+            if let PatKind::Ident(
+                _,
+                SpannedIdent {
+                    node: Ident { name, .. },
+                    ..
+                },
+                ..
+            ) = arg.pat.node
+            {
+                if name != Symbol::intern("self") {
+                    errors.push(
+                        Error::new(
+                            arg.pat.span,
+                            "Static methods are not supported.",
+                        ).help("take `&self` as the first argument"),
+                    );
+                }
+            } else {
+                errors.push(Error::new(arg.pat.span, "Unexpected pattern."));
+            }
+        },
+        None => {
+            errors.push(
+                Error::new(span, "Static methods are not supported.")
+                    .help("take `&self` as the first argument"),
+            )
+        },
+    }
+
+    for arg in sig.decl.inputs.iter().skip(1) {
+        match arg.pat.node {
+            PatKind::Ident(..) if options.computed.is_some() => {
+                errors.push(Error::new(
+                    arg.pat.span,
+                    "A computed property can't take arguments.",
+                ))
+            },
+            PatKind::Ident(..) => {},
+            _ => errors.push(Error::new(arg.pat.span, "Unexpected pattern.")),
+        }
+
+        match arg.ty.node {
+            TyKind::Rptr(_, MutTy { mutbl, .. })
+                if options.dynamic && mutbl == Mutability::Mutable =>
+            {
+                errors.push(Error::new(
+                    arg.ty.span,
+                    "Dynamic signals can't take mutable references.",
+                ))
+            },
+            _ => {},
+        }
+    }
+
+    match sig.decl.output {
+        FunctionRetTy::Ty(ref ty) if options.fallible.is_some() => {
+            if sig_fallible_error(&options, ty).is_none() {
+                errors.push(Error::new(ty.span, sig_fallible_message(&options)))
+            }
+        },
+        FunctionRetTy::Default(span) if options.fallible.is_some() => {
+            errors.push(Error::new(span, sig_fallible_message(&options)))
+        },
+        FunctionRetTy::Ty(ref ty) if options.computed.is_none() => {
+            errors.push(
+                Error::new(ty.span, "A signal can only return an empty value.")
+                    .help(
+                        "a signal declared with #[sig(fallible)] returns \
+                         Result<(), E>, and a method declared with \
+                         #[sig(computed(...))] returns its value",
+                    ),
+            )
+        },
+        FunctionRetTy::Default(span) if options.computed.is_some() => {
+            errors.push(Error::new(
+                span,
+                "A computed property must return its value.",
+            ))
+        },
+        _ => {},
+    }
+
+    errors
+}
+
+fn sig_report(cx: &ExtCtxt, errors: &[Error]) {
+    for error in errors {
+        let mut diagnostic = cx.struct_span_err(error.span, error.message);
+
+        if let Some(help) = error.help {
+            diagnostic.help(help);
+        }
+
+        if let Some((span, note)) = error.note {
+            diagnostic.span_note(span, note);
+        }
+
+        if let Some((span, message, suggestion)) = error.suggestion {
+            diagnostic.span_suggestion(span, message, suggestion.to_owned());
+        }
+
+        diagnostic.emit();
+    }
+}
+
+fn sig_lifetimes(
+    storage: Storage,
+    inputs: &[Arg],
+    options: &Options,
+) -> Vec<Error>
+{
+    let (message, borrowed) = if storage == Storage::Table {
+        (
+            "Signals of enumerations keep their slots in a global table, \
+             which can't borrow for a named lifetime.",
+            false,
+        )
+    } else if options.dynamic {
+        (
+            "Dynamic signals pass their arguments as `Any`, which can't \
+             borrow for a named lifetime.",
+            true,
+        )
+    } else {
+        return vec![];
+    };
+
+    inputs
+        .iter()
+        .flat_map(|arg| {
+            let mut lifetimes = SigLifetimes(vec![]);
+
+            match arg.ty.node {
+                TyKind::Rptr(_, MutTy { ref ty, .. }) if borrowed => {
+                    lifetimes.visit_ty(ty)
+                },
+                _ => lifetimes.visit_ty(&arg.ty),
+            }
+
+            lifetimes.0.into_iter().map(move |lifetime| {
+                Error::new(arg.ty.span, message).suggest(
+                    lifetime.span,
+                    "add `'static` to this argument type",
+                    "'static",
+                )
+            })
+        })
+        .collect()
+}
+
+fn sig_check(
+    cx: &mut ExtCtxt,
+    span: Span,
+    attrs: &[Attribute],
+    sig: &MethodSig,
+) -> bool
+{
+    let errors = sig_errors(span, attrs, sig);
+
+    sig_report(cx, &errors);
+
+    errors.is_empty()
+}
+
+fn sig_ty_params<'a>(ty: &'a Ty, name: &str) -> Option<&'a [P<Ty>]> {
+    let segment = match ty.node {
+        TyKind::Path(None, ref path) => path.segments.last()?,
+        _ => return None,
+    };
+
+    match segment.parameters.as_ref().map(|parameters| &**parameters) {
+        Some(&PathParameters::AngleBracketed(ref data))
+            if segment.identifier.name == name =>
+        {
+            Some(&data.types)
+        },
+        _ => None,
+    }
+}
+
+fn sig_fallible_error<'a>(options: &Options, ty: &'a Ty) -> Option<&'a P<Ty>> {
+    let types = sig_ty_params(ty, "Result")?;
+    let unit = match types.first().map(|ty| &ty.node) {
+        Some(&TyKind::Tup(ref types)) => types.is_empty(),
+        _ => false,
+    };
+
+    if types.len() != 2 || !unit {
+        return None;
+    }
+
+    let error = &types[1];
+
+    match options.fallible {
+        Some(mode) if mode.name == "collect_emit" => {
+            sig_ty_params(error, "Vec").and_then(|types| {
+                if types.len() == 1 {
+                    types.first()
+                } else {
+                    None
+                }
+            })
+        },
+        Some(_) => Some(error),
+        None => None,
+    }
+}
+
+fn sig_fallible_message(options: &Options) -> &'static str {
+    match options.fallible {
+        Some(mode) if mode.name == "collect_emit" => {
+            "A signal that collects the errors of its slots must return \
+             Result<(), Vec<E>>."
+        },
+        _ => "A fallible signal must return Result<(), E>.",
+    }
+}
+
+fn sig_target(visited: &[&Mod], ty: &Ty) -> Option<usize> {
+    sig_resolve(visited, ty).and_then(|(found, _)| {
+        visited.iter().position(|&module| ptr::eq(module, found))
+    })
+}
+
+fn sig_cloned(visited: &[&Mod], ty: &Ty) -> bool {
+    sig_resolve(visited, ty)
+        .map_or(false, |(_, item)| sig_derives(&item.attrs, "Clone"))
+}
+
+fn sig_clone_policy(options: &mut Options, cloned: bool) {
+    if options.clone.is_none() && !cloned {
+        options.clone = Some(Ident::from_str("Disconnect"));
+    }
+}
+
+fn sig_signal_options(
+    cx: &ExtCtxt,
+    attrs: &[Attribute],
+    sig: &MethodSig,
+    cloned: bool,
+) -> Options
+{
+    let (mut options, _) = sig_options(attrs);
+
+    sig_clone_policy(&mut options, cloned);
+    let error = match sig.decl.output {
+        FunctionRetTy::Ty(ref ty) => sig_fallible_error(&options, ty),
+        FunctionRetTy::Default(_) => None,
+    };
+    let result = if options.cancellable {
+        Some(cx.ty_path(cx.path_global(DUMMY_SP, sig_runtime("Propagation"))))
+    } else {
+        error.map(|error| {
+            cx.ty_path(cx.path_all(
+                DUMMY_SP,
+                true,
+                cx.std_path(&["result", "Result"]),
+                vec![],
+                vec![cx.ty(DUMMY_SP, TyKind::Tup(vec![])), error.clone()],
+                vec![],
+            ))
+        })
+    };
+
+    Options { result, ..options }
+}
+
+fn sig_slot_ty(cx: &ExtCtxt, inputs: &[Arg], options: &Options) -> P<Ty> {
+    let slot = cx.ty_path(Path {
+        span: DUMMY_SP,
+        segments: vec![
+            PathSegment {
+                identifier: Ident::from_str(if options.mutable {
+                    "FnMut"
+                } else {
+                    "Fn"
+                }),
+                span: DUMMY_SP,
+                parameters: Some(P(PathParameters::Parenthesized(
+                    ParenthesizedParameterData {
+                        span: DUMMY_SP,
+                        inputs: inputs
+                            .iter()
+                            .map(|arg| arg.ty.clone())
+                            .collect(),
+                        output: options.result.clone(),
+                    },
+                ))),
+            },
+        ],
+    });
+
+    if options.mutable {
+        cx.ty_path(cx.path_all(
+            DUMMY_SP,
+            true,
+            cx.std_path(&["cell", "RefCell"]),
+            vec![],
+            vec![slot],
+            vec![],
+        ))
+    } else {
+        slot
+    }
+}
+
+fn sig_boxed(storage: Storage, options: &Options) -> bool {
+    storage != Storage::Table &&
+        options.clone.map_or(false, |policy| policy.name == "Disconnect")
+}
+
+fn sig_runtime(name: &str) -> Vec<Ident> {
+    vec![Ident::from_str("sig_runtime"), Ident::from_str(name)]
+}
+
+fn sig_slots_ty(cx: &ExtCtxt, inputs: &[Arg], options: &Options) -> P<Ty> {
+    let policy = options.clone.map(|policy| {
+        cx.ty_path(cx.path_global(
+            DUMMY_SP,
+            sig_runtime(&policy.name.as_str()),
+        ))
+    });
+
+    cx.ty_path(cx.path_all(
+        DUMMY_SP,
+        true,
+        sig_runtime("Slots"),
+        vec![],
+        [
+            vec![sig_slot_ty(cx, inputs, options)],
+            policy.into_iter().collect(),
+        ].concat(),
+        vec![],
+    ))
+}
+
+fn sig_field(
+    cx: &ExtCtxt,
+    span: Span,
+    ident: Ident,
+    inputs: &[Arg],
+    options: &Options,
+) -> StructField
+{
+    SigSpan(span).fold_struct_field(StructField {
+        span: DUMMY_SP,
+        ident: Some(ident),
+        vis: Visibility {
+            node: VisibilityKind::Inherited,
+            span: DUMMY_SP,
+        },
+        id: DUMMY_NODE_ID,
+        ty: sig_slots_ty(cx, inputs, options),
+        attrs: vec![],
+    })
+}
+
+fn sig_table(
+    cx: &ExtCtxt,
+    function: &str,
+    ty: &P<Ty>,
+    inputs: &[Arg],
+    options: &Options,
+    args: Vec<P<Expr>>,
+) -> P<Expr>
+{
+    cx.expr_call(
+        DUMMY_SP,
+        cx.expr_path(cx.path_all(
+            DUMMY_SP,
+            true,
+            sig_runtime(function),
+            vec![],
+            vec![ty.clone(), sig_slot_ty(cx, inputs, options)],
+            vec![],
+        )),
+        args,
+    )
+}
+
+fn sig_args(cx: &ExtCtxt, inputs: &[Arg]) -> Vec<P<Expr>> {
+    inputs
+        .iter()
+        .map(|arg| {
+            if let PatKind::Ident(_, ident, _) = arg.pat.node {
+                cx.expr_ident(DUMMY_SP, ident.node)
+            } else {
+                cx.expr_ident(DUMMY_SP, Ident::from_str(""))
+            }
+        })
+        .collect()
+}
+
+fn sig_emit(
+    cx: &ExtCtxt,
+    storage: Storage,
+    ty: &P<Ty>,
+    ident: Ident,
+    inputs: &[Arg],
+    options: &Options,
+) -> P<Expr>
+{
+    let name = cx.expr_str(DUMMY_SP, ident.name);
+    let sender = match storage {
+        Storage::Trait => {
+            cx.expr_method_call(
+                DUMMY_SP,
+                cx.expr_self(DUMMY_SP),
+                sig_sender_ident(ident),
+                vec![],
+            )
+        },
+        _ => {
+            let sender = pprust::ty_to_string(ty);
+
+            cx.expr_str(DUMMY_SP, Symbol::intern(&sender))
+        },
+    };
+    let policy = match options.panic {
+        Some(policy) => {
+            cx.expr_some(
+                DUMMY_SP,
+                cx.expr_path(cx.path_global(
+                    DUMMY_SP,
+                    [sig_runtime("PanicPolicy"), vec![policy]].concat(),
+                )),
+            )
+        },
+        None => cx.expr_none(DUMMY_SP),
+    };
+    let slot = Ident::from_str("slot");
+    let callee = if options.mutable {
+        cx.expr_mut_addr_of(
+            DUMMY_SP,
+            cx.expr_deref(
+                DUMMY_SP,
+                cx.expr_call_global(
+                    DUMMY_SP,
+                    sig_runtime("borrow_slot"),
+                    vec![name.clone(), cx.expr_ident(DUMMY_SP, slot)],
+                ),
+            ),
+        )
+    } else {
+        cx.expr_ident(DUMMY_SP, slot)
+    };
+    let call = cx.lambda1(
+        DUMMY_SP,
+        cx.expr_call(DUMMY_SP, callee, sig_args(cx, inputs)),
+        slot,
+    );
+    let slots = match storage {
+        Storage::Field => {
+            cx.expr_addr_of(
+                DUMMY_SP,
+                sig_slots(cx, storage, ty, ident, inputs, options),
+            )
+        },
+        Storage::Table => {
+            cx.expr_addr_of(
+                DUMMY_SP,
+                sig_table(
+                    cx,
+                    "keyed_slots",
+                    ty,
+                    inputs,
+                    options,
+                    vec![
+                        name.clone(),
+                        cx.expr_addr_of(DUMMY_SP, cx.expr_self(DUMMY_SP)),
+                    ],
+                ),
+            )
+        },
+        Storage::Trait => sig_slots(cx, storage, ty, ident, inputs, options),
+    };
+    let method = if options.cancellable {
+        Ident::from_str("cancellable_emit")
+    } else {
+        options.fallible.unwrap_or_else(|| Ident::from_str("emit"))
+    };
+
+    cx.expr_call_global(
+        DUMMY_SP,
+        [sig_runtime("Emit"), vec![method]].concat(),
+        vec![slots, name, sender, policy, call],
+    )
+}
+
+fn sig_ident(prefix: &str, ident: Ident) -> Ident {
+    Ident::from_str(&format!("{}_{}", prefix, ident.name))
+}
+
+fn sig_accessor_ident(ident: Ident, mutbl: Mutability) -> Ident {
+    match mutbl {
+        Mutability::Immutable => {
+            Ident::from_str(&format!("{}_slots", ident.name))
+        },
+        Mutability::Mutable => {
+            Ident::from_str(&format!("{}_slots_mut", ident.name))
+        },
+    }
+}
+
+fn sig_sender_ident(ident: Ident) -> Ident {
+    Ident::from_str(&format!("{}_sender", ident.name))
+}
+
+fn sig_sender(cx: &ExtCtxt) -> MethodSig {
+    sig_method_sig(
+        vec![sig_self(Mutability::Immutable)],
+        FunctionRetTy::Ty(cx.ty_rptr(
+            DUMMY_SP,
+            cx.ty_ident(DUMMY_SP, Ident::from_str("str")),
+            Some(cx.lifetime(DUMMY_SP, keywords::StaticLifetime.ident())),
+            Mutability::Immutable,
+        )),
+    )
+}
+
+fn sig_self(mutbl: Mutability) -> Arg {
+    Arg::from_self(
+        respan(DUMMY_SP, SelfKind::Region(None, mutbl)),
+        respan(DUMMY_SP, keywords::SelfValue.ident()),
+    )
+}
+
+fn sig_method_sig(inputs: Vec<Arg>, output: FunctionRetTy) -> MethodSig {
+    MethodSig {
+        unsafety: Unsafety::Normal,
+        constness: respan(DUMMY_SP, Constness::NotConst),
+        abi: Abi::Rust,
+        decl: P(FnDecl {
+            inputs,
+            output,
+            variadic: false,
+        }),
+    }
+}
+
+fn sig_accessor(
+    cx: &ExtCtxt,
+    inputs: &[Arg],
+    options: &Options,
+    mutbl: Mutability,
+) -> MethodSig
+{
+    sig_method_sig(
+        vec![sig_self(mutbl)],
+        FunctionRetTy::Ty(cx.ty_rptr(
+            DUMMY_SP,
+            sig_slots_ty(cx, inputs, options),
+            None,
+            mutbl,
+        )),
+    )
+}
+
+fn sig_slots(
+    cx: &ExtCtxt,
+    storage: Storage,
+    ty: &P<Ty>,
+    ident: Ident,
+    inputs: &[Arg],
+    options: &Options,
+) -> P<Expr>
+{
+    match storage {
+        Storage::Field => {
+            cx.expr_field_access(DUMMY_SP, cx.expr_self(DUMMY_SP), ident)
+        },
+        Storage::Table => {
+            sig_table(
+                cx,
+                "slots",
+                ty,
+                inputs,
+                options,
+                vec![
+                    cx.expr_str(DUMMY_SP, ident.name),
+                    cx.expr_addr_of(DUMMY_SP, cx.expr_self(DUMMY_SP)),
+                ],
+            )
+        },
+        Storage::Trait => {
+            cx.expr_method_call(
+                DUMMY_SP,
+                cx.expr_self(DUMMY_SP),
+                sig_accessor_ident(ident, Mutability::Immutable),
+                vec![],
+            )
+        },
+    }
+}
+
+fn sig_methods(
+    cx: &ExtCtxt,
+    storage: Storage,
+    ty: &P<Ty>,
+    ident: Ident,
+    inputs: &[Arg],
+    output: FunctionRetTy,
+    options: &Options,
+) -> Vec<ImplItem>
+{
+    let name = cx.expr_str(DUMMY_SP, ident.name);
+    let slot = cx.expr_ident(DUMMY_SP, Ident::from_str("slot"));
+    let slot = if sig_boxed(storage, options) {
+        slot
+    } else {
+        cx.expr_call_global(
+            DUMMY_SP,
+            cx.std_path(&["rc", "Rc", "from"]),
+            vec![slot],
+        )
+    };
+    let (mutbl, connect, disconnect) = match storage {
+        Storage::Field => {
+            let field = || {
+                cx.expr_field_access(DUMMY_SP, cx.expr_self(DUMMY_SP), ident)
+            };
+
+            (
+                Mutability::Mutable,
+                cx.expr_method_call(
+                    DUMMY_SP,
+                    field(),
+                    Ident::from_str("connect"),
+                    vec![slot],
+                ),
+                cx.expr_method_call(
+                    DUMMY_SP,
+                    field(),
+                    Ident::from_str("disconnect_all"),
+                    vec![],
+                ),
+            )
+        },
+        Storage::Table => {
+            let key = cx.expr_addr_of(DUMMY_SP, cx.expr_self(DUMMY_SP));
+
+            (
+                Mutability::Immutable,
+                sig_table(
+                    cx,
+                    "connect",
+                    ty,
+                    inputs,
+                    options,
+                    vec![name.clone(), key.clone(), slot],
+                ),
+                sig_table(
+                    cx,
+                    "disconnect",
+                    ty,
+                    inputs,
+                    options,
+                    vec![name, key],
+                ),
+            )
+        },
+        Storage::Trait => {
+            let accessor = |mutbl| {
+                cx.expr_method_call(
+                    DUMMY_SP,
+                    cx.expr_self(DUMMY_SP),
+                    sig_accessor_ident(ident, mutbl),
+                    vec![],
+                )
+            };
+
+            (
+                Mutability::Mutable,
+                cx.expr_method_call(
+                    DUMMY_SP,
+                    accessor(Mutability::Mutable),
+                    Ident::from_str("connect"),
+                    vec![slot],
+                ),
+                cx.expr_method_call(
+                    DUMMY_SP,
+                    accessor(Mutability::Mutable),
+                    Ident::from_str("disconnect_all"),
+                    vec![],
+                ),
+            )
+        },
+    };
+    let slot_ty = cx.ty_path(cx.path_all(
+        DUMMY_SP,
+        true,
+        cx.std_path(&["boxed", "Box"]),
+        vec![],
+        vec![sig_slot_ty(cx, inputs, options)],
+        vec![],
+    ));
+    let emit = sig_emit(cx, storage, ty, ident, inputs, options);
+    let emit = if options.result.is_some() {
+        cx.block_expr(emit)
+    } else {
+        cx.block(DUMMY_SP, vec![cx.stmt_semi(emit)])
+    };
+    let output = match options.result {
+        Some(ref result) if options.cancellable => {
+            FunctionRetTy::Ty(result.clone())
+        },
+        _ => output,
+    };
+    let connect_vis = options.connect.clone().unwrap_or(VisibilityKind::Public);
+    let emit_vis = options.emit.clone().unwrap_or(VisibilityKind::Inherited);
+
+    let methods = vec![
+        sig_fn(
+            sig_ident("connect", ident),
+            connect_vis.clone(),
+            sig_method_sig(
+                vec![
+                    sig_self(mutbl),
+                    cx.arg(DUMMY_SP, Ident::from_str("slot"), slot_ty),
+                ],
+                FunctionRetTy::Default(DUMMY_SP),
+            ),
+            cx.block(DUMMY_SP, vec![cx.stmt_semi(connect)]),
+        ),
+        sig_fn(
+            sig_ident("disconnect", ident),
+            connect_vis,
+            sig_method_sig(
+                vec![sig_self(mutbl)],
+                FunctionRetTy::Default(DUMMY_SP),
+            ),
+            cx.block(DUMMY_SP, vec![cx.stmt_semi(disconnect)]),
+        ),
+        sig_fn(
+            sig_ident("emit", ident),
+            emit_vis,
+            sig_method_sig(
+                [vec![sig_self(Mutability::Immutable)], inputs.to_vec()]
+                    .concat(),
+                output,
+            ),
+            emit,
+        ),
+    ];
+
+    methods
+        .into_iter()
+        .map(|mut method| {
+            method.attrs.push(sig_allow(cx, "dead_code"));
+
+            method
+        })
+        .collect()
+}
+
+fn sig_body(
+    cx: &ExtCtxt,
+    span: Span,
+    ident: Ident,
+    inputs: &[Arg],
+    options: &Options,
+    stmts: &[Stmt],
+) -> P<Block>
+{
+    let emit = cx.expr_method_call(
+        DUMMY_SP,
+        cx.expr_self(DUMMY_SP),
+        sig_ident("emit", ident),
+        sig_args(cx, inputs),
+    );
+
+    if options.cancellable && !stmts.is_empty() {
+        let stopped = cx.expr_binary(
+            DUMMY_SP,
+            BinOpKind::Eq,
+            emit,
+            cx.expr_path(cx.path_global(
+                DUMMY_SP,
+                [sig_runtime("Propagation"), vec![Ident::from_str("Stop")]]
+                    .concat(),
+            )),
+        );
+        let cancel = cx.block(
+            DUMMY_SP,
+            vec![cx.stmt_semi(cx.expr(DUMMY_SP, ExprKind::Ret(None)))],
+        );
+
+        cx.block(
+            span,
+            [
+                vec![cx.stmt_expr(cx.expr_if(
+                    DUMMY_SP,
+                    stopped,
+                    cx.expr_block(cancel),
+                    None,
+                ))],
+                stmts.to_vec(),
+            ].concat(),
+        )
+    } else if options.fallible.is_none() {
+        cx.block(span, [stmts.to_vec(), vec![cx.stmt_semi(emit)]].concat())
+    } else if stmts.is_empty() {
+        cx.block(span, vec![cx.stmt_expr(emit)])
+    } else {
+        cx.block(
+            span,
+            [
+                vec![cx.stmt_semi(cx.expr(DUMMY_SP, ExprKind::Try(emit)))],
+                stmts.to_vec(),
+            ].concat(),
+        )
+    }
+}
+
+fn sig_default_expr(cx: &ExtCtxt) -> P<Expr> {
+    cx.expr_call_global(
+        DUMMY_SP,
+        cx.std_path(&["default", "Default", "default"]),
+        vec![],
+    )
+}
+
+fn sig_fields(cx: &ExtCtxt, fields: &[StructField]) -> Vec<Field> {
+    fields
+        .iter()
+        .filter_map(|field| field.ident)
+        .map(|ident| cx.field_imm(DUMMY_SP, ident, sig_default_expr(cx)))
+        .collect()
+}
+
+fn sig_ty_ident(ty: &Ty) -> Option<Ident> {
+    if let TyKind::Path(None, ref path) = ty.node {
+        path.segments.last().map(|segment| segment.identifier)
+    } else {
+        None
+    }
+}
+
+fn sig_self_ty(cx: &ExtCtxt, ident: Ident, generics: &Generics) -> P<Ty> {
+    let lifetimes = generics
+        .params
+        .iter()
+        .filter_map(|param| match *param {
+            GenericParam::Lifetime(ref def) => Some(def.lifetime),
+            _ => None,
+        })
+        .collect();
+    let types = generics
+        .params
+        .iter()
+        .filter_map(|param| match *param {
+            GenericParam::Type(ref param) => {
+                Some(cx.ty_ident(DUMMY_SP, param.ident))
+            },
+            _ => None,
+        })
+        .collect();
+
+    cx.ty_path(cx.path_all(
+        DUMMY_SP,
+        false,
+        vec![ident],
+        lifetimes,
+        types,
+        vec![],
+    ))
+}
+
+fn sig_fn(
+    ident: Ident,
+    vis: VisibilityKind,
+    sig: MethodSig,
+    body: P<Block>,
+) -> ImplItem
+{
+    ImplItem {
+        id: DUMMY_NODE_ID,
+        ident,
+        vis: Visibility {
+            node: vis,
+            span: DUMMY_SP,
+        },
+        defaultness: Defaultness::Final,
+        attrs: vec![],
+        generics: Generics::default(),
+        node: ImplItemKind::Method(sig, body),
+        span: DUMMY_SP,
+        tokens: None,
+    }
+}
+
+fn sig_impl(
+    cx: &ExtCtxt,
+    generics: &Generics,
+    trait_ref: Option<Path>,
+    ty: P<Ty>,
+    items: Vec<ImplItem>,
+) -> P<Item>
+{
+    cx.item(
+        DUMMY_SP,
+        keywords::Invalid.ident(),
+        vec![],
+        ItemKind::Impl(
+            Unsafety::Normal,
+            ImplPolarity::Positive,
+            Defaultness::Final,
+            generics.clone(),
+            trait_ref.map(|path| {
+                TraitRef {
+                    path,
+                    ref_id: DUMMY_NODE_ID,
+                }
+            }),
+            ty,
+            items,
+        ),
+    )
+}
+
+fn sig_impls<'a>(
+    visited: &[&'a Mod],
+    ident: Ident,
+) -> Vec<&'a [ImplItem]>
+{
+    let module = match visited.last() {
+        Some(&module) => module,
+        None => return vec![],
+    };
+
+    sig_scopes(&visited[..1])
+        .into_iter()
+        .flat_map(|scope| {
+            let items =
+                scope.last().map_or(&[][..], |&module| module.items.as_slice());
+
+            items
+                .iter()
+                .filter_map(|item| match item.node {
+                    ItemKind::Impl(_, _, _, _, None, ref ty, ref items) => {
+                        sig_resolve(&scope, ty).and_then(|(found, item)| {
+                            if ptr::eq(found, module) &&
+                                item.ident.name == ident.name
+                            {
+                                Some(items.as_slice())
+                            } else {
+                                None
+                            }
+                        })
+                    },
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+fn sig_has_new(visited: &[&Mod], ident: Ident) -> bool {
+    sig_impls(visited, ident)
+        .iter()
+        .any(|items| items.iter().any(|item| item.ident.name == "new"))
+}
+
+fn sig_vis_rank(vis: &VisibilityKind) -> usize {
+    match *vis {
+        VisibilityKind::Public => 3,
+        VisibilityKind::Crate(_) => 2,
+        VisibilityKind::Restricted { .. } => 1,
+        VisibilityKind::Inherited => 0,
+    }
+}
+
+fn sig_min_vis(vis: VisibilityKind, other: &VisibilityKind) -> VisibilityKind {
+    let conflict = match (&vis, other) {
+        (
+            &VisibilityKind::Restricted { ref path, .. },
+            &VisibilityKind::Restricted {
+                path: ref other_path,
+                ..
+            },
+        ) => path != other_path,
+        _ => false,
+    };
+
+    if conflict {
+        VisibilityKind::Inherited
+    } else if sig_vis_rank(other) < sig_vis_rank(&vis) {
+        other.clone()
+    } else {
+        vis
+    }
+}
+
+fn sig_constructors(
+    cx: &ExtCtxt,
+    visited: &[&Mod],
+    item: &Item,
+    injected: &Item,
+) -> Vec<P<Item>>
+{
+    let (content, generics) = match item.node {
+        ItemKind::Struct(ref content, ref generics) => (content, generics),
+        _ => return vec![],
+    };
+    let own = content.fields();
+    let fields = match injected.node {
+        ItemKind::Struct(ref content, _) => content.fields(),
+        _ => return vec![],
+    };
+
+    if fields.len() == own.len() {
+        return vec![];
+    }
+
+    let ty = sig_self_ty(cx, item.ident, generics);
+    let signals = fields[own.len()..]
+        .iter()
+        .map(|_| sig_default_expr(cx))
+        .collect::<Vec<_>>();
+    let mut constructors = vec![];
+
+    if let VariantData::Unit(_) = *content {
+        if !sig_derives(&item.attrs, "Default") {
+            let default = sig_fn(
+                Ident::from_str("default"),
+                VisibilityKind::Inherited,
+                sig_method_sig(vec![], FunctionRetTy::Ty(ty.clone())),
+                cx.block_expr(cx.expr_struct_ident(
+                    DUMMY_SP,
+                    item.ident,
+                    sig_fields(cx, fields),
+                )),
+            );
+
+            constructors.push(sig_impl(
+                cx,
+                generics,
+                Some(cx.path_global(
+                    DUMMY_SP,
+                    cx.std_path(&["default", "Default"]),
+                )),
+                ty.clone(),
+                vec![default],
+            ));
+        }
+    }
+
+    if !sig_has_new(visited, item.ident) {
+        let args = own
+            .iter()
+            .enumerate()
+            .map(|(number, field)| {
+                field.ident.unwrap_or_else(|| {
+                    Ident::from_str(&format!("field{}", number))
+                })
+            })
+            .collect::<Vec<_>>();
+        let body = match injected.node {
+            ItemKind::Struct(VariantData::Tuple(..), _) => {
+                cx.expr_call_ident(
+                    DUMMY_SP,
+                    item.ident,
+                    [
+                        args.iter()
+                            .map(|&arg| cx.expr_ident(DUMMY_SP, arg))
+                            .collect(),
+                        signals,
+                    ].concat(),
+                )
+            },
+            _ => {
+                cx.expr_struct_ident(
+                    DUMMY_SP,
+                    item.ident,
+                    [
+                        args.iter()
+                            .map(|&arg| {
+                                cx.field_imm(
+                                    DUMMY_SP,
+                                    arg,
+                                    cx.expr_ident(DUMMY_SP, arg),
+                                )
+                            })
+                            .collect(),
+                        sig_fields(cx, &fields[own.len()..]),
+                    ].concat(),
+                )
+            },
+        };
+        let vis = own.iter().fold(item.vis.node.clone(), |vis, field| {
+            sig_min_vis(vis, &field.vis.node)
+        });
+        let mut new = sig_fn(
+            Ident::from_str("new"),
+            vis,
+            sig_method_sig(
+                args.iter()
+                    .zip(own.iter())
+                    .map(|(&arg, field)| {
+                        cx.arg(DUMMY_SP, arg, field.ty.clone())
+                    })
+                    .collect(),
+                FunctionRetTy::Ty(ty.clone()),
+            ),
+            cx.block_expr(body),
+        );
+
+        new.attrs.push(sig_allow(cx, "dead_code"));
+        constructors.push(sig_impl(cx, generics, None, ty, vec![new]));
+    }
+
+    constructors
+}
+
+fn sig_property(
+    cx: &mut ExtCtxt,
+    ty: &P<Ty>,
+    cloned: bool,
+    field: &StructField,
+    dependents: &[Ident],
+) -> Option<(Signal, StructField, Vec<ImplItem>)>
+{
+    let vis = &field.vis.node;
+    let (mut options, mut errors) = sig_options(&field.attrs);
+
+    sig_clone_policy(&mut options, cloned);
+
+    if !options.notify {
+        errors.push(Error::new(
+            field.span,
+            "A field can only be declared as a property with \
+             #[sig(notify)].",
+        ));
+    }
+
+    if options.fallible.is_some() {
+        errors.push(Error::new(field.span, "Properties can't be fallible."));
+    }
+
+    if options.cancellable {
+        errors.push(Error::new(field.span, "Properties can't be cancellable."));
+    }
+
+    if options.mutable {
+        errors.push(
+            Error::new(field.span, "Properties can't have mutable slots.")
+                .help("properties can be bound, which needs `Fn` slots"),
+        );
+    }
+
+    sig_report(cx, &errors);
+
+    let ident = match field.ident {
+        Some(ident) if errors.is_empty() => ident,
+        _ => return None,
+    };
+    let signal = Ident::from_str(&format!("{}_changed", ident.name));
+    let ref_ty =
+        cx.ty_rptr(DUMMY_SP, field.ty.clone(), None, Mutability::Immutable);
+    let inputs = vec![
+        cx.arg(DUMMY_SP, Ident::from_str("old"), ref_ty.clone()),
+        cx.arg(DUMMY_SP, Ident::from_str("new"), ref_ty.clone()),
+    ];
+    let old = Ident::from_str("old");
+    let value = Ident::from_str("value");
+    let access =
+        || cx.expr_field_access(DUMMY_SP, cx.expr_self(DUMMY_SP), ident);
+    let getter = sig_fn(
+        ident,
+        vis.clone(),
+        sig_method_sig(
+            vec![sig_self(Mutability::Immutable)],
+            FunctionRetTy::Ty(ref_ty),
+        ),
+        cx.block_expr(cx.expr_addr_of(DUMMY_SP, access())),
+    );
+    let updates = dependents
+        .iter()
+        .map(|&dependent| {
+            cx.stmt_semi(cx.expr_method_call(
+                DUMMY_SP,
+                cx.expr_self(DUMMY_SP),
+                sig_ident("update", dependent),
+                vec![],
+            ))
+        })
+        .collect::<Vec<_>>();
+    let changed = cx.block(
+        DUMMY_SP,
+        vec![
+            cx.stmt_let(
+                DUMMY_SP,
+                false,
+                old,
+                cx.expr_call_global(
+                    DUMMY_SP,
+                    cx.std_path(&["mem", "replace"]),
+                    vec![
+                        cx.expr_mut_addr_of(DUMMY_SP, access()),
+                        cx.expr_ident(DUMMY_SP, value),
+                    ],
+                ),
+            ),
+            cx.stmt_semi(cx.expr_method_call(
+                DUMMY_SP,
+                cx.expr_self(DUMMY_SP),
+                sig_ident("emit", signal),
+                vec![
+                    cx.expr_addr_of(DUMMY_SP, cx.expr_ident(DUMMY_SP, old)),
+                    cx.expr_addr_of(DUMMY_SP, access()),
+                ],
+            )),
+        ],
+    );
+    let setter = sig_fn(
+        sig_ident("set", ident),
+        vis.clone(),
+        sig_method_sig(
+            vec![
+                sig_self(Mutability::Mutable),
+                cx.arg(DUMMY_SP, value, field.ty.clone()),
+            ],
+            FunctionRetTy::Default(DUMMY_SP),
+        ),
+        cx.block(
+            DUMMY_SP,
+            vec![cx.stmt_expr(cx.expr_if(
+                DUMMY_SP,
+                cx.expr_binary(
+                    DUMMY_SP,
+                    BinOpKind::Ne,
+                    access(),
+                    cx.expr_ident(DUMMY_SP, value),
+                ),
+                cx.expr_block(changed),
+                None,
+            ))],
+        ),
+    );
+    let accessor = |ident| {
+        cx.expr_path(cx.path(
+            DUMMY_SP,
+            vec![keywords::SelfType.ident(), ident],
+        ))
+    };
+    let connect = accessor(sig_ident("connect", signal));
+    let property = sig_fn(
+        Ident::from_str(&format!("{}_property", ident.name)),
+        options.connect.clone().unwrap_or_else(|| vis.clone()),
+        sig_method_sig(
+            vec![],
+            FunctionRetTy::Ty(cx.ty_path(cx.path_all(
+                DUMMY_SP,
+                true,
+                sig_runtime("Property"),
+                vec![],
+                vec![
+                    cx.ty_ident(DUMMY_SP, keywords::SelfType.ident()),
+                    field.ty.clone(),
+                ],
+                vec![],
+            ))),
+        ),
+        cx.block_expr(cx.expr_call_global(
+            DUMMY_SP,
+            [sig_runtime("Property"), vec![Ident::from_str("new")]].concat(),
+            vec![
+                accessor(ident),
+                accessor(sig_ident("set", ident)),
+                connect,
+            ],
+        )),
+    );
+    let emit = sig_ident("emit", signal);
+    let methods = sig_methods(
+        cx,
+        Storage::Field,
+        ty,
+        signal,
+        &inputs,
+        FunctionRetTy::Default(DUMMY_SP),
+        &options,
+    ).into_iter()
+        .map(|method| match method.node {
+            ImplItemKind::Method(ref sig, ref body)
+                if method.ident.name == emit.name =>
+            {
+                let body = body.clone().map(|body| {
+                    Block {
+                        stmts: [updates.clone(), body.stmts].concat(),
+                        ..body
+                    }
+                });
+
+                ImplItem {
+                    node: ImplItemKind::Method(sig.clone(), body),
+                    ..method.clone()
+                }
+            },
+            _ => method,
+        })
+        .collect::<Vec<_>>();
+
+    Some((
+        Signal {
+            span: field.span,
+            ident: signal,
+            inputs: inputs.clone(),
+            storage: Storage::Field,
+            options: options.clone(),
+        },
+        sig_field(cx, field.span, signal, &inputs, &options),
+        [vec![getter, setter, property], methods]
+            .concat()
+            .into_iter()
+            .flat_map(|item| SigSpan(field.span).fold_impl_item(item))
+            .collect(),
+    ))
+}
+
+fn sig_computed_items(
+    visited: &[&Mod],
+    ident: Ident,
+) -> Vec<(Span, Ident, Vec<Ident>)>
+{
+    sig_impls(visited, ident)
+        .into_iter()
+        .flat_map(|items| items.iter())
+        .filter(|item| sig_find(&item.attrs))
+        .filter_map(|item| {
+            sig_options(&item.attrs)
+                .0
+                .computed
+                .map(|dependencies| (item.span, item.ident, dependencies))
+        })
+        .collect()
+}
+
+fn sig_dependencies(
+    cx: &ExtCtxt,
+    visited: &[&Mod],
+    ty: &Ty,
+    items: &[ImplItem],
+) {
+    let resolved = sig_resolve(visited, ty).map(|(_, item)| &item.node);
+    let properties = match resolved {
+        Some(&ItemKind::Struct(VariantData::Struct(ref fields, _), _)) => {
+            fields
+                .iter()
+                .filter(|field| sig_find(&field.attrs))
+                .filter_map(|field| field.ident)
+                .collect::<Vec<_>>()
+        },
+        _ => return,
+    };
+
+    for item in items.iter().filter(|item| sig_find(&item.attrs)) {
+        let dependencies = match sig_options(&item.attrs).0.computed {
+            Some(dependencies) => dependencies,
+            None => continue,
+        };
+
+        if dependencies.iter().any(|dependency| {
+            !properties
+                .iter()
+                .any(|property| property.name == dependency.name)
+        }) {
+            cx.span_err(
+                item.span,
+                "A computed property can only depend on properties of its \
+                 structure.",
+            );
+        }
+    }
+}
+
+fn sig_properties(
+    cx: &mut ExtCtxt,
+    visited: &[&Mod],
+    injects: &mut [Vec<Inject>],
+    item: &P<Item>,
+) -> (P<Item>, Option<P<Item>>)
+{
+    let (fields, node_id, generics) = match item.node {
+        ItemKind::Struct(
+            VariantData::Struct(ref fields, node_id),
+            ref generics,
+        ) => (fields, node_id, generics),
+        ItemKind::Struct(VariantData::Tuple(ref fields, _), _) => {
+            for field in fields.iter().filter(|field| sig_find(&field.attrs)) {
+                cx.span_err(field.span, "Only named fields can be properties.");
+            }
+
+            return (item.clone(), None);
+        },
+        _ => return (item.clone(), None),
+    };
+
+    if !fields.iter().any(|field| sig_find(&field.attrs)) {
+        return (item.clone(), None);
+    }
+
+    let computed = sig_computed_items(visited, item.ident);
+    let ty = sig_self_ty(cx, item.ident, generics);
+    let cloned = sig_derives(&item.attrs, "Clone");
+    let mut signals = vec![];
+    let mut slots = vec![];
+    let mut methods = vec![];
+
+    for field in fields.iter().filter(|field| sig_find(&field.attrs)) {
+        let dependents = computed
+            .iter()
+            .filter(|&&(_, _, ref dependencies)| {
+                dependencies.iter().any(|dependency| {
+                    field
+                        .ident
+                        .map_or(false, |ident| ident.name == dependency.name)
+                })
+            })
+            .map(|&(_, ident, _)| ident)
+            .collect::<Vec<_>>();
+        let property = sig_property(cx, &ty, cloned, field, &dependents);
+
+        if let Some((signal, field, items)) = property {
+            signals.push(signal);
+            slots.push(field);
+            methods.extend(items);
+        }
+    }
+
+    let level = injects.len() - 1;
+
+    injects[level].push(Inject {
+        ty: cx.ty_ident(DUMMY_SP, item.ident),
+        target: Some(level),
+        fields: slots,
+        signals,
+    });
+
+    let fields = fields
+        .iter()
+        .map(|field| {
+            StructField {
+                attrs: sig_strip(&field.attrs),
+                ..field.clone()
+            }
+        })
+        .collect();
+
+    (
+        P(Item {
+            ident: item.ident,
+            attrs: item.attrs.clone(),
+            id: item.id,
+            node: ItemKind::Struct(
+                VariantData::Struct(fields, node_id),
+                generics.clone(),
+            ),
+            vis: item.vis.clone(),
+            span: item.span,
+            tokens: item.tokens.clone(),
+        }),
+        Some(sig_impl(cx, generics, None, ty, methods).map(|mut item| {
+            item.attrs.push(sig_allow(cx, "dead_code"));
+
+            item
+        })),
+    )
+}
+
+fn sig_computed(
+    cx: &mut ExtCtxt,
+    injects: &mut [Vec<Inject>],
+    storage: Storage,
+    ty: &P<Ty>,
+    span: Span,
+    ident: Ident,
+    sig: &MethodSig,
+    body: &P<Block>,
+    options: &Options,
+) -> (MethodSig, P<Block>, Vec<ImplItem>)
+{
+    let value_ty = match sig.decl.output {
+        FunctionRetTy::Ty(ref ty) if storage == Storage::Field => ty.clone(),
+        _ => {
+            cx.span_err(
+                span,
+                "Computed properties are only supported for structures.",
+            );
+
+            return (sig.clone(), body.clone(), vec![]);
+        },
+    };
+    let signal = Ident::from_str(&format!("{}_changed", ident.name));
+    let inputs = vec![cx.arg(
+        DUMMY_SP,
+        Ident::from_str("value"),
+        cx.ty_rptr(DUMMY_SP, value_ty.clone(), None, Mutability::Immutable),
+    )];
+    let cache = StructField {
+        ty: cx.ty_path(cx.path_all(
+            span,
+            true,
+            sig_runtime("Computed"),
+            vec![],
+            vec![value_ty],
+            vec![],
+        )),
+        ..sig_field(cx, span, ident, &inputs, options)
+    };
+
+    injects[injects.len() - 1].push(Inject {
+        ty: ty.clone(),
+        target: None,
+        fields: vec![cache, sig_field(cx, span, signal, &inputs, options)],
+        signals: vec![Signal {
+            span,
+            ident: signal,
+            inputs: inputs.clone(),
+            storage: Storage::Field,
+            options: options.clone(),
+        }],
+    });
+
+    let old = Ident::from_str("old");
+    let new = Ident::from_str("new");
+    let field =
+        |ident| cx.expr_field_access(DUMMY_SP, cx.expr_self(DUMMY_SP), ident);
+    let getter = cx.block_expr(cx.expr_method_call(
+        DUMMY_SP,
+        field(ident),
+        Ident::from_str("get"),
+        vec![cx.lambda0(DUMMY_SP, cx.expr_block(body.clone()))],
+    ));
+    let emit = cx.block(
+        DUMMY_SP,
+        vec![cx.stmt_semi(cx.expr_method_call(
+            DUMMY_SP,
+            cx.expr_self(DUMMY_SP),
+            sig_ident("emit", signal),
+            vec![cx.expr_addr_of(DUMMY_SP, cx.expr_ident(DUMMY_SP, new))],
+        ))],
+    );
+    let changed = cx.block(
+        DUMMY_SP,
+        vec![
+            cx.stmt_let(
+                DUMMY_SP,
+                false,
+                new,
+                cx.expr_method_call(
+                    DUMMY_SP,
+                    cx.expr_self(DUMMY_SP),
+                    ident,
+                    vec![],
+                ),
+            ),
+            cx.stmt_expr(cx.expr_if(
+                DUMMY_SP,
+                cx.expr_method_call(
+                    DUMMY_SP,
+                    cx.expr_ident(DUMMY_SP, old),
+                    Ident::from_str("map_or"),
+                    vec![
+                        cx.expr_bool(DUMMY_SP, false),
+                        cx.lambda1(
+                            DUMMY_SP,
+                            cx.expr_binary(
+                                DUMMY_SP,
+                                BinOpKind::Ne,
+                                cx.expr_ident(DUMMY_SP, old),
+                                cx.expr_ident(DUMMY_SP, new),
+                            ),
+                            old,
+                        ),
+                    ],
+                ),
+                cx.expr_block(emit),
+                None,
+            )),
+        ],
+    );
+    let mut update = sig_fn(
+        sig_ident("update", ident),
+        VisibilityKind::Crate(CrateSugar::PubCrate),
+        sig_method_sig(
+            vec![sig_self(Mutability::Immutable)],
+            FunctionRetTy::Default(DUMMY_SP),
+        ),
+        cx.block(
+            DUMMY_SP,
+            vec![
+                cx.stmt_let(
+                    DUMMY_SP,
+                    false,
+                    old,
+                    cx.expr_method_call(
+                        DUMMY_SP,
+                        field(ident),
+                        Ident::from_str("invalidate"),
+                        vec![],
+                    ),
+                ),
+                cx.stmt_expr(cx.expr_if(
+                    DUMMY_SP,
+                    cx.expr_unary(
+                        DUMMY_SP,
+                        UnOp::Not,
+                        cx.expr_method_call(
+                            DUMMY_SP,
+                            field(signal),
+                            Ident::from_str("is_empty"),
+                            vec![],
+                        ),
+                    ),
+                    cx.expr_block(changed),
+                    None,
+                )),
+            ],
+        ),
+    );
+    let methods = sig_methods(
+        cx,
+        Storage::Field,
+        ty,
+        signal,
+        &inputs,
+        FunctionRetTy::Default(DUMMY_SP),
+        options,
+    );
+
+    update.attrs.push(sig_allow(cx, "dead_code"));
+
+    (sig.clone(), getter, [vec![update], methods].concat())
+}
+
+fn sig_signals<'a>(injects: &'a [Inject], item: &Item) -> Vec<&'a Signal> {
+    injects
+        .iter()
+        .filter(|inject| {
+            sig_ty_ident(&inject.ty)
+                .map_or(false, |ident| ident.name == item.ident.name)
+        })
+        .flat_map(|inject| inject.signals.iter())
+        .collect()
+}
+
+fn sig_vec(cx: &ExtCtxt, exprs: Vec<P<Expr>>) -> P<Expr> {
+    let tts = exprs
+        .iter()
+        .flat_map(|expr| {
+            let comma = TokenTree::Token(DUMMY_SP, Token::Comma);
+
+            [expr.to_tokens(cx), vec![comma]].concat()
+        })
+        .collect::<TokenStream>();
+
+    cx.expr(
+        DUMMY_SP,
+        ExprKind::Mac(respan(
+            DUMMY_SP,
+            Mac_ {
+                path: cx.path_ident(DUMMY_SP, Ident::from_str("vec")),
+                tts: tts.into(),
+            },
+        )),
+    )
+}
+
+fn sig_meta(cx: &ExtCtxt, injects: &[Inject], item: &Item) -> Option<P<Item>> {
+    let generics = match item.node {
+        ItemKind::Struct(_, ref generics) | ItemKind::Enum(_, ref generics) => {
+            generics
+        },
+        _ => return None,
+    };
+    let ty = sig_self_ty(cx, item.ident, generics);
+    let metas = sig_signals(injects, item)
+        .into_iter()
+        .map(|signal| {
+            let args = signal
+                .inputs
+                .iter()
+                .map(|arg| {
+                    let name = pprust::ty_to_string(&arg.ty);
+
+                    cx.expr_str(DUMMY_SP, Symbol::intern(&name))
+                })
+                .collect();
+            let slots = sig_slots(
+                cx,
+                signal.storage,
+                &ty,
+                signal.ident,
+                &signal.inputs,
+                &signal.options,
+            );
+
+            cx.expr_call_global(
+                DUMMY_SP,
+                [sig_runtime("SignalMeta"), vec![Ident::from_str("new")]]
+                    .concat(),
+                vec![
+                    cx.expr_str(DUMMY_SP, signal.ident.name),
+                    cx.expr_vec_slice(DUMMY_SP, args),
+                    cx.expr_method_call(
+                        DUMMY_SP,
+                        slots,
+                        Ident::from_str("len"),
+                        vec![],
+                    ),
+                ],
+            )
+        })
+        .collect::<Vec<_>>();
+
+    if metas.is_empty() {
+        return None;
+    }
+
+    let signals = sig_fn(
+        Ident::from_str("signals"),
+        VisibilityKind::Inherited,
+        sig_method_sig(
+            vec![sig_self(Mutability::Immutable)],
+            FunctionRetTy::Ty(cx.ty_path(cx.path_all(
+                DUMMY_SP,
+                true,
+                cx.std_path(&["vec", "Vec"]),
+                vec![],
+                vec![cx.ty_path(cx.path_global(
+                    DUMMY_SP,
+                    sig_runtime("SignalMeta"),
+                ))],
+                vec![],
+            ))),
+        ),
+        cx.block_expr(sig_vec(cx, metas)),
+    );
+
+    Some(sig_impl(
+        cx,
+        generics,
+        Some(cx.path_global(DUMMY_SP, sig_runtime("HasSignals"))),
+        ty,
+        vec![signals],
+    ))
+}
+
+fn sig_any_ty(cx: &ExtCtxt) -> P<Ty> {
+    cx.ty_rptr(
+        DUMMY_SP,
+        cx.ty_path(cx.path_global(DUMMY_SP, cx.std_path(&["any", "Any"]))),
+        None,
+        Mutability::Immutable,
+    )
+}
+
+fn sig_dyn_result_ty(cx: &ExtCtxt) -> P<Ty> {
+    cx.ty_path(cx.path_all(
+        DUMMY_SP,
+        true,
+        cx.std_path(&["result", "Result"]),
+        vec![],
+        vec![
+            cx.ty(DUMMY_SP, TyKind::Tup(vec![])),
+            cx.ty_path(cx.path_global(DUMMY_SP, sig_runtime("DynError"))),
+        ],
+        vec![],
+    ))
+}
+
+fn sig_dyn_error(cx: &ExtCtxt, name: &str, args: Vec<P<Expr>>) -> P<Expr> {
+    cx.expr_err(
+        DUMMY_SP,
+        cx.expr_call_global(
+            DUMMY_SP,
+            [sig_runtime("DynError"), vec![Ident::from_str(name)]].concat(),
+            args,
+        ),
+    )
+}
+
+fn sig_public(vis: &Option<VisibilityKind>, default: bool) -> bool {
+    match *vis {
+        Some(VisibilityKind::Public) => true,
+        Some(_) => false,
+        None => default,
+    }
+}
+
+fn sig_dyn_arm(
+    cx: &ExtCtxt,
+    signal: &Signal,
+    public: bool,
+    stmts: fn(&ExtCtxt, &Signal) -> Vec<Stmt>,
+) -> Arm
+{
+    let name = cx.expr_str(DUMMY_SP, signal.ident.name);
+    let body = if !signal.options.dynamic {
+        sig_dyn_error(cx, "NotDynamic", vec![name.clone()])
+    } else if public {
+        cx.expr_block(cx.block(
+            DUMMY_SP,
+            [
+                stmts(cx, signal),
+                vec![cx.stmt_expr(cx.expr_ok(
+                    DUMMY_SP,
+                    cx.expr(DUMMY_SP, ExprKind::Tup(vec![])),
+                ))],
+            ].concat(),
+        ))
+    } else {
+        sig_dyn_error(cx, "Private", vec![name.clone()])
+    };
+
+    cx.arm(DUMMY_SP, vec![cx.pat_lit(DUMMY_SP, name)], body)
+}
+
+fn sig_dyn_connect(cx: &ExtCtxt, signal: &Signal) -> Vec<Stmt> {
+    let any = sig_any_ty(cx);
+    let args = signal
+        .inputs
+        .iter()
+        .zip(sig_args(cx, &signal.inputs))
+        .map(|(input, arg)| {
+            let arg = match input.ty.node {
+                TyKind::Rptr(_, MutTy { ref ty, .. }) => {
+                    if sig_ty_ident(ty).map_or(false, |ty| ty.name == "str") {
+                        cx.expr_addr_of(
+                            DUMMY_SP,
+                            cx.expr_method_call(
+                                DUMMY_SP,
+                                arg,
+                                Ident::from_str("to_owned"),
+                                vec![],
+                            ),
+                        )
+                    } else {
+                        arg
+                    }
+                },
+                _ => cx.expr_addr_of(DUMMY_SP, arg),
+            };
+
+            cx.expr_cast(DUMMY_SP, arg, any.clone())
+        })
+        .collect();
+    let slot = cx
+        .lambda_fn_decl(
+            DUMMY_SP,
+            P(FnDecl {
+                inputs: signal.inputs.clone(),
+                output: FunctionRetTy::Default(DUMMY_SP),
+                variadic: false,
+            }),
+            cx.expr_call_ident(
+                DUMMY_SP,
+                Ident::from_str("slot"),
+                vec![cx.expr_vec_slice(DUMMY_SP, args)],
+            ),
+            DUMMY_SP,
+        )
+        .map(|mut slot| {
+            if let ExprKind::Closure(ref mut capture, ..) = slot.node {
+                *capture = CaptureBy::Value;
+            }
+
+            slot
+        });
+    let slot = if signal.options.mutable {
+        cx.expr_call_global(
+            DUMMY_SP,
+            cx.std_path(&["cell", "RefCell", "new"]),
+            vec![slot],
+        )
+    } else {
+        slot
+    };
+
+    vec![cx.stmt_semi(cx.expr_method_call(
+        DUMMY_SP,
+        cx.expr_self(DUMMY_SP),
+        sig_ident("connect", signal.ident),
+        vec![cx.expr_call_global(
+            DUMMY_SP,
+            cx.std_path(&["boxed", "Box", "new"]),
+            vec![slot],
+        )],
+    ))]
+}
+
+fn sig_dyn_emit(cx: &ExtCtxt, signal: &Signal) -> Vec<Stmt> {
+    let name = cx.expr_str(DUMMY_SP, signal.ident.name);
+    let args = cx.expr_ident(DUMMY_SP, Ident::from_str("args"));
+    let attempt = |expr| cx.expr(DUMMY_SP, ExprKind::Try(expr));
+    let check = cx.expr_call_global(
+        DUMMY_SP,
+        sig_runtime("dyn_args"),
+        vec![
+            name.clone(),
+            args.clone(),
+            cx.expr_usize(DUMMY_SP, signal.inputs.len()),
+        ],
+    );
+    let values = signal
+        .inputs
+        .iter()
+        .enumerate()
+        .map(|(index, input)| {
+            let downcast = |ty: &P<Ty>| {
+                let expected = pprust::ty_to_string(ty);
+
+                attempt(cx.expr_call(
+                    DUMMY_SP,
+                    cx.expr_path(cx.path_all(
+                        DUMMY_SP,
+                        true,
+                        sig_runtime("dyn_arg"),
+                        vec![],
+                        vec![ty.clone()],
+                        vec![],
+                    )),
+                    vec![
+                        name.clone(),
+                        args.clone(),
+                        cx.expr_usize(DUMMY_SP, index),
+                        cx.expr_str(DUMMY_SP, Symbol::intern(&expected)),
+                    ],
+                ))
+            };
+
+            match input.ty.node {
+                TyKind::Rptr(_, MutTy { ref ty, .. }) => {
+                    if sig_ty_ident(ty).map_or(false, |ty| ty.name == "str") {
+                        attempt(cx.expr_call_global(
+                            DUMMY_SP,
+                            sig_runtime("dyn_str"),
+                            vec![
+                                name.clone(),
+                                args.clone(),
+                                cx.expr_usize(DUMMY_SP, index),
+                            ],
+                        ))
+                    } else {
+                        downcast(ty)
+                    }
+                },
+                _ => {
+                    cx.expr_method_call(
+                        DUMMY_SP,
+                        downcast(&input.ty),
+                        Ident::from_str("clone"),
+                        vec![],
+                    )
+                },
+            }
+        })
+        .collect();
+
+    vec![
+        cx.stmt_semi(attempt(check)),
+        cx.stmt_semi(cx.expr_method_call(
+            DUMMY_SP,
+            cx.expr_self(DUMMY_SP),
+            sig_ident("emit", signal.ident),
+            values,
+        )),
+    ]
+}
+
+fn sig_dyn(cx: &ExtCtxt, injects: &[Inject], item: &Item) -> Option<P<Item>> {
+    let generics = match item.node {
+        ItemKind::Struct(_, ref generics) | ItemKind::Enum(_, ref generics) => {
+            generics
+        },
+        _ => return None,
+    };
+    let signals = sig_signals(injects, item);
+
+    if !signals.iter().any(|signal| signal.options.dynamic) {
+        return None;
+    }
+
+    let str_ty = cx.ty_rptr(
+        DUMMY_SP,
+        cx.ty_ident(DUMMY_SP, Ident::from_str("str")),
+        None,
+        Mutability::Immutable,
+    );
+    let unknown = |cx: &ExtCtxt| {
+        cx.arm(
+            DUMMY_SP,
+            vec![cx.pat_wild(DUMMY_SP)],
+            sig_dyn_error(
+                cx,
+                "UnknownSignal",
+                vec![cx.expr_method_call(
+                    DUMMY_SP,
+                    cx.expr_ident(DUMMY_SP, Ident::from_str("signal")),
+                    Ident::from_str("to_owned"),
+                    vec![],
+                )],
+            ),
+        )
+    };
+    let dispatch = |cx: &ExtCtxt, arms: Vec<Arm>| {
+        cx.block_expr(cx.expr_match(
+            DUMMY_SP,
+            cx.expr_ident(DUMMY_SP, Ident::from_str("signal")),
+            [arms, vec![unknown(cx)]].concat(),
+        ))
+    };
+    let connect = sig_fn(
+        Ident::from_str("connect_dyn"),
+        VisibilityKind::Inherited,
+        sig_method_sig(
+            vec![
+                sig_self(Mutability::Mutable),
+                cx.arg(DUMMY_SP, Ident::from_str("signal"), str_ty.clone()),
+                cx.arg(
+                    DUMMY_SP,
+                    Ident::from_str("slot"),
+                    cx.ty_path(cx.path_global(
+                        DUMMY_SP,
+                        sig_runtime("DynSlot"),
+                    )),
+                ),
+            ],
+            FunctionRetTy::Ty(sig_dyn_result_ty(cx)),
+        ),
+        dispatch(
+            cx,
+            signals
+                .iter()
+                .map(|signal| {
+                    let public = sig_public(&signal.options.connect, true);
+
+                    sig_dyn_arm(cx, signal, public, sig_dyn_connect)
+                })
+                .collect(),
+        ),
+    );
+    let emit = sig_fn(
+        Ident::from_str("emit_dyn"),
+        VisibilityKind::Inherited,
+        sig_method_sig(
+            vec![
+                sig_self(Mutability::Immutable),
+                cx.arg(DUMMY_SP, Ident::from_str("signal"), str_ty),
+                cx.arg(
+                    DUMMY_SP,
+                    Ident::from_str("args"),
+                    cx.ty_rptr(
+                        DUMMY_SP,
+                        cx.ty(DUMMY_SP, TyKind::Slice(sig_any_ty(cx))),
+                        None,
+                        Mutability::Immutable,
+                    ),
+                ),
+            ],
+            FunctionRetTy::Ty(sig_dyn_result_ty(cx)),
+        ),
+        dispatch(
+            cx,
+            signals
+                .iter()
+                .map(|signal| {
+                    let public = sig_public(&signal.options.emit, false);
+
+                    sig_dyn_arm(cx, signal, public, sig_dyn_emit)
+                })
+                .collect(),
+        ),
+    );
+
+    let allow = sig_allow(cx, "unused_variables");
+
+    Some(
+        sig_impl(
+            cx,
+            generics,
+            Some(cx.path_global(DUMMY_SP, sig_runtime("DynSignals"))),
+            sig_self_ty(cx, item.ident, generics),
+            vec![connect, emit],
+        ).map(|mut item| {
+            item.attrs.push(allow);
+
+            item
+        }),
+    )
+}
+
+fn sig_method(
+    cx: &mut ExtCtxt,
+    injects: &mut [Vec<Inject>],
+    storage: Storage,
+    cloned: bool,
+    ty: &P<Ty>,
+    span: Span,
+    ident: Ident,
+    attrs: &[Attribute],
+    sig: &MethodSig,
+    body: &P<Block>,
+) -> (MethodSig, P<Block>, Vec<ImplItem>)
+{
+    if sig_find(attrs) && sig_check(cx, span, attrs, sig) {
+        let inputs = &sig.decl.inputs[1..];
+        let options = sig_signal_options(cx, attrs, sig, cloned);
+        let errors = sig_lifetimes(storage, inputs, &options);
+
+        if !errors.is_empty() {
+            sig_report(cx, &errors);
+
+            return (sig.clone(), body.clone(), vec![]);
+        }
+
+        if options.computed.is_some() {
+            return sig_computed(
+                cx,
+                injects,
+                storage,
+                ty,
+                span,
+                ident,
+                sig,
+                body,
+                &options,
+            );
+        }
+
+        let fields = match storage {
+            Storage::Field => {
+                vec![sig_field(cx, span, ident, inputs, &options)]
+            },
+            _ => vec![],
+        };
+
+        injects[injects.len() - 1].push(Inject {
+            ty: ty.clone(),
+            target: None,
+            fields,
+            signals: vec![Signal {
+                span,
+                ident,
+                inputs: inputs.to_vec(),
+                storage,
+                options: options.clone(),
+            }],
+        });
+
+        (
+            sig.clone(),
+            sig_body(cx, body.span, ident, inputs, &options, &body.stmts),
+            sig_methods(
+                cx,
+                storage,
+                ty,
+                ident,
+                inputs,
+                sig.decl.output.clone(),
+                &options,
+            ),
+        )
+    } else {
+        (sig.clone(), body.clone(), vec![])
+    }
+}
+
+fn sig_hidden(cx: &ExtCtxt) -> Attribute {
+    cx.attribute(
+        DUMMY_SP,
+        cx.meta_list(
+            DUMMY_SP,
+            Symbol::intern("doc"),
+            vec![cx.meta_list_item_word(DUMMY_SP, Symbol::intern("hidden"))],
+        ),
+    )
+}
+
+fn sig_allow(cx: &ExtCtxt, lint: &str) -> Attribute {
+    cx.attribute(
+        DUMMY_SP,
+        cx.meta_list(
+            DUMMY_SP,
+            Symbol::intern("allow"),
+            vec![cx.meta_list_item_word(DUMMY_SP, Symbol::intern(lint))],
+        ),
+    )
+}
+
+fn sig_trait_fn(item: ImplItem) -> TraitItem {
+    TraitItem {
+        id: item.id,
+        ident: item.ident,
+        attrs: item.attrs,
+        generics: item.generics,
+        node: match item.node {
+            ImplItemKind::Method(sig, body) => {
+                TraitItemKind::Method(sig, Some(body))
+            },
+            _ => unreachable!(),
+        },
+        span: item.span,
+        tokens: item.tokens,
+    }
+}
+
+fn sig_trait_item(
+    cx: &mut ExtCtxt,
+    ident: Ident,
+    item: &TraitItem,
+) -> Vec<TraitItem>
+{
+    let stripped = TraitItem {
+        attrs: sig_strip(&item.attrs),
+        ..item.clone()
+    };
+
+    match item.node {
+        TraitItemKind::Method(ref sig, ref body) if sig_find(&item.attrs) => {
+            if sig_check(cx, item.span, &item.attrs, sig) {
+                let inputs = &sig.decl.inputs[1..];
+                let options = sig_signal_options(cx, &item.attrs, sig, true);
+                let errors = sig_lifetimes(Storage::Trait, inputs, &options);
+
+                if !errors.is_empty() {
+                    sig_report(cx, &errors);
+
+                    return vec![stripped];
+                }
+
+                if options.connect.is_some() || options.emit.is_some() {
+                    cx.span_err(
+                        item.span,
+                        "Signals declared in traits are as visible as the \
+                         trait, so their visibility can't be changed.",
+                    );
+                }
+
+                if options.computed.is_some() {
+                    cx.span_err(
+                        item.span,
+                        "Computed properties can't be declared in traits.",
+                    );
+                }
+
+                let accessors = [Mutability::Immutable, Mutability::Mutable]
+                    .iter()
+                    .map(|&mutbl| {
+                        TraitItem {
+                            id: DUMMY_NODE_ID,
+                            ident: sig_accessor_ident(item.ident, mutbl),
+                            attrs: vec![sig_hidden(cx)],
+                            generics: Generics::default(),
+                            node: TraitItemKind::Method(
+                                sig_accessor(cx, inputs, &options, mutbl),
+                                None,
+                            ),
+                            span: item.span,
+                            tokens: None,
+                        }
+                    })
+                    .chain(Some(TraitItem {
+                        id: DUMMY_NODE_ID,
+                        ident: sig_sender_ident(item.ident),
+                        attrs: vec![sig_hidden(cx)],
+                        generics: Generics::default(),
+                        node: TraitItemKind::Method(sig_sender(cx), None),
+                        span: item.span,
+                        tokens: None,
+                    }))
+                    .collect::<Vec<_>>();
+                let methods = sig_methods(
+                    cx,
+                    Storage::Trait,
+                    &cx.ty_ident(DUMMY_SP, ident),
+                    item.ident,
+                    inputs,
+                    sig.decl.output.clone(),
+                    &options,
+                ).into_iter()
+                    .map(|mut method| {
+                        let emit = sig_ident("emit", item.ident);
+
+                        if method.ident.name == emit.name {
+                            method.attrs.push(sig_hidden(cx));
+                        }
+
+                        method
+                    })
+                    .map(sig_trait_fn)
+                    .collect::<Vec<_>>();
+                let stmts = body.as_ref()
+                    .map_or_else(Vec::new, |body| body.stmts.clone());
+
+                let method = TraitItem {
+                    id: item.id,
+                    ident: item.ident,
+                    attrs: stripped.attrs,
+                    generics: item.generics.clone(),
+                    node: TraitItemKind::Method(
+                        sig.clone(),
+                        Some(sig_body(
+                            cx,
+                            body.as_ref().map_or(item.span, |body| body.span),
+                            item.ident,
+                            inputs,
+                            &options,
+                            &stmts,
+                        )),
+                    ),
+                    span: item.span,
+                    tokens: item.tokens.clone(),
+                };
+
+                [accessors, methods, vec![method]]
+                    .concat()
+                    .into_iter()
+                    .flat_map(|method| {
+                        SigSpan(item.span).fold_trait_item(method)
                     })
+                    .collect()
             } else {
+                vec![stripped]
+            }
+        },
+        _ => {
+            if sig_find(&item.attrs) {
                 cx.span_err(
-                    inject.0.span,
-                    "The structure for which the signals are implemented is \
-                     not found in the current module. Please define the \
-                     structure in the same location as the implementation. \
-                     Perhaps in the future, this restriction will be removed.",
+                    item.span,
+                    "Using sig attribute is only allowed for methods and \
+                     crates",
                 );
+            }
+
+            vec![stripped]
+        },
+    }
+}
+
+fn sig_traits<'a>(module: &'a Mod, ident: Ident) -> Option<&'a [TraitItem]> {
+    module
+        .items
+        .iter()
+        .filter_map(|item| match item.node {
+            ItemKind::Trait(_, _, _, _, ref items)
+                if item.ident.name == ident.name =>
+            {
+                Some(items.as_slice())
+            },
+            _ => None,
+        })
+        .next()
+}
+
+fn sig_submodules(module: &Mod) -> Vec<&Mod> {
+    module
+        .items
+        .iter()
+        .filter_map(|item| match item.node {
+            ItemKind::Mod(ref module) => Some(module),
+            _ => None,
+        })
+        .flat_map(|module| [vec![module], sig_submodules(module)].concat())
+        .collect()
+}
+
+fn sig_scopes<'a>(visited: &[&'a Mod]) -> Vec<Vec<&'a Mod>> {
+    let module = match visited.last() {
+        Some(&module) => module,
+        None => return vec![],
+    };
+
+    module
+        .items
+        .iter()
+        .filter_map(|item| match item.node {
+            ItemKind::Mod(ref module) => Some(module),
+            _ => None,
+        })
+        .flat_map(|module| sig_scopes(&[visited, &[module]].concat()))
+        .chain(Some(visited.to_vec()))
+        .collect()
+}
 
-                None
-            }.map(|(number, item)| {
-                (
-                    number,
+fn sig_resolve<'a>(
+    visited: &[&'a Mod],
+    ty: &Ty,
+) -> Option<(&'a Mod, &'a Item)>
+{
+    let path = match ty.node {
+        TyKind::Path(None, ref path) => path,
+        _ => return None,
+    };
+    let (segment, modules) = path.segments.split_last()?;
+    let find = |module: &'a Mod| {
+        module
+            .items
+            .iter()
+            .find(|item| {
+                item.ident.name == segment.identifier.name &&
                     match item.node {
-                        ItemKind::Struct(ref content, ref generics) => {
-                            ItemKind::Struct(
-                                match content {
-                                    &VariantData::Struct(
-                                        ref fields,
-                                        node_id,
-                                    ) => {
-                                        VariantData::Struct(
-                                            [fields.clone(), inject.1.clone()]
-                                                .concat(),
-                                            node_id,
-                                        )
-                                    },
-                                    &VariantData::Tuple(
-                                        ref fields,
-                                        node_id,
-                                    ) => {
-                                        VariantData::Tuple(
-                                            [fields.clone(), inject.1.clone()]
-                                                .concat(),
-                                            node_id,
-                                        )
-                                    },
-                                    content => {
-                                        cx.span_err(
-                                            item.span,
-                                            "Only structures and tuple \
-                                             structures are supported.",
-                                        );
-
-                                        content.clone()
-                                    },
-                                },
-                                generics.clone(),
-                            )
-                        },
-                        ref node => {
-                            cx.span_err(
-                                item.span,
-                                "Only structures and tuple structures are \
-                                 supported.",
-                            );
-
-                            node.clone()
-                        },
-                    },
-                )
+                        ItemKind::Struct(..) |
+                        ItemKind::Enum(..) |
+                        ItemKind::Union(..) => true,
+                        _ => false,
+                    }
             })
-                .map_or_else(
-                    || module.clone(),
-                    |(number, item_node)| {
-                        let module = module.clone();
-                        let mut items = module.items;
-                        let item = items[number].clone();
-                        let item = P(Item {
-                            ident: item.ident,
-                            attrs: item.attrs.clone(),
-                            id: item.id,
-                            node: item_node,
-                            vis: item.vis.clone(),
-                            span: item.span,
-                            tokens: item.tokens.clone(),
-                        });
+            .map(|item| (module, &**item))
+    };
 
-                        items[number] = item;
+    if modules.is_empty() {
+        visited.iter().rev().filter_map(|&module| find(module)).next()
+    } else {
+        sig_module(visited, modules).and_then(find)
+    }
+}
 
-                        Mod {
-                            inner: module.inner,
-                            items,
-                        }
+fn sig_module<'a>(
+    visited: &[&'a Mod],
+    segments: &[PathSegment],
+) -> Option<&'a Mod>
+{
+    let mut modules = visited.to_vec();
+
+    for segment in segments {
+        let name = segment.identifier.name;
+
+        if name == keywords::CrateRoot.name() || name == keywords::Crate.name()
+        {
+            modules.truncate(1);
+        } else if name == keywords::Super.name() {
+            if modules.len() < 2 {
+                return None;
+            }
+
+            modules.pop();
+        } else if name != keywords::SelfValue.name() {
+            let module = modules.last()?
+                .items
+                .iter()
+                .filter_map(|item| match item.node {
+                    ItemKind::Mod(ref module) if item.ident.name == name => {
+                        Some(module)
                     },
-                )
+                    _ => None,
+                })
+                .next()?;
+
+            modules.push(module);
+        }
+    }
+
+    modules.last().cloned()
+}
+
+fn sig_imports(
+    tree: &UseTree,
+    prefix: &[PathSegment],
+    ident: Ident,
+) -> Vec<(Vec<PathSegment>, bool)>
+{
+    let mut path = [prefix, &tree.prefix.segments[..]].concat();
+
+    match tree.kind {
+        UseTreeKind::Simple(rename) => {
+            let module = path.last().map_or(false, |last| {
+                last.identifier.name == keywords::SelfValue.name()
+            });
+
+            if module {
+                path.pop();
+            }
+
+            let imported = rename
+                .or_else(|| path.last().map(|last| last.identifier))
+                .map_or(false, |name| name.name == ident.name);
+
+            if imported {
+                vec![(path, false)]
+            } else {
+                vec![]
+            }
+        },
+        UseTreeKind::Glob => {
+            path.push(PathSegment::from_ident(ident, DUMMY_SP));
+
+            vec![(path, true)]
+        },
+        UseTreeKind::Nested(ref trees) => trees
+            .iter()
+            .flat_map(|&(ref tree, _)| sig_imports(tree, &path, ident))
+            .collect(),
+    }
+}
+
+fn sig_imported<'a>(
+    visited: &[&'a Mod],
+    ident: Ident,
+) -> Option<&'a [TraitItem]>
+{
+    let current = visited[visited.len() - 1];
+    let imports = current
+        .items
+        .iter()
+        .filter_map(|item| match item.node {
+            ItemKind::Use(ref tree) => Some(tree),
+            _ => None,
         })
+        .flat_map(|tree| sig_imports(tree, &[], ident))
+        .collect::<Vec<_>>();
+
+    for (path, glob) in imports {
+        let (segment, modules) = match path.split_last() {
+            Some(segments) => segments,
+            None => continue,
+        };
+        let relative = modules.first().map_or(false, |first| {
+            first.identifier.name == keywords::SelfValue.name() ||
+                first.identifier.name == keywords::Super.name()
+        });
+        let scope = if relative { visited } else { &visited[..1] };
+        let items = sig_module(scope, modules)
+            .and_then(|module| sig_traits(module, segment.identifier));
+
+        match items {
+            Some(items) => return Some(items),
+            None if !glob => return Some(&[]),
+            None => {},
+        }
+    }
+
+    None
 }
 
-fn sig_method(
+fn sig_trait_items<'a>(
+    visited: &[&'a Mod],
+    path: &Path,
+) -> Result<&'a [TraitItem], Error>
+{
+    let (segment, modules) = match path.segments.split_last() {
+        Some(segments) => segments,
+        None => return Ok(&[]),
+    };
+
+    if !modules.is_empty() {
+        return Ok(sig_module(visited, modules)
+            .and_then(|module| sig_traits(module, segment.identifier))
+            .unwrap_or(&[]));
+    }
+
+    let current = visited[visited.len() - 1];
+
+    if let Some(items) = sig_traits(current, segment.identifier) {
+        return Ok(items);
+    }
+
+    if let Some(items) = sig_imported(visited, segment.identifier) {
+        return Ok(items);
+    }
+
+    let traits = [vec![visited[0]], sig_submodules(visited[0])]
+        .concat()
+        .into_iter()
+        .filter_map(|module| sig_traits(module, segment.identifier))
+        .filter(|items| items.iter().any(|item| sig_find(&item.attrs)))
+        .collect::<Vec<_>>();
+
+    match traits.len() {
+        0 => Ok(&[]),
+        1 => Ok(traits[0]),
+        _ => Err(Error::new(
+            path.span,
+            "Several traits of this name declare signals, so it's ambiguous \
+             which signals to implement.",
+        ).help(
+            "write the path of the trait, e.g. `self::Trait` or \
+             `super::module::Trait`",
+        )),
+    }
+}
+
+fn sig_trait_impl_items(
     cx: &mut ExtCtxt,
-    injects: &mut [Vec<(P<Ty>, Vec<StructField>)>],
+    visited: &[&Mod],
+    injects: &mut [Vec<Inject>],
+    storage: Storage,
     ty: &P<Ty>,
-    ident: Ident,
-    attrs: &[Attribute],
-    sig: &MethodSig,
-    body: &P<Block>,
-) -> (MethodSig, P<Block>)
+    path: &Path,
+) -> Vec<ImplItem>
 {
-    attrs
+    let items = match sig_trait_items(visited, path) {
+        Ok(items) => items,
+        Err(error) => {
+            sig_report(cx, &[error]);
+
+            return vec![];
+        },
+    };
+    let signals = items
         .iter()
-        .find(|attr| {
-            attr.path ==
-                Path::from_ident(attr.path.span, Ident::from_str("sig"))
-        })
-        .and_then(|_| sig.decl.inputs.get(0))
-        .and_then(|arg| {
-            if let PatKind::Ident(
-                _,
-                SpannedIdent {
-                    node: Ident { name, .. },
-                    ..
-                },
-                ..
-            ) = arg.pat.node
+        .filter_map(|item| match item.node {
+            TraitItemKind::Method(ref sig, _)
+                if sig_find(&item.attrs) &&
+                    sig_errors(item.span, &item.attrs, sig).is_empty() =>
             {
-                if name == Symbol::intern("self") {
-                    Some(arg)
-                } else {
-                    cx.span_err(
-                        arg.pat.span,
-                        "Static methods are not supported.",
-                    );
+                Some((
+                    item.span,
+                    item.ident,
+                    &sig.decl.inputs[1..],
+                    sig_signal_options(cx, &item.attrs, sig, true),
+                ))
+            },
+            _ => None,
+        })
+        .collect::<Vec<_>>();
 
-                    None
-                }
-            } else {
-                cx.span_err(arg.pat.span, "Unexpected pattern.");
+    if storage == Storage::Table && !signals.is_empty() {
+        cx.span_err(
+            ty.span,
+            "Signals declared in traits are only supported for structures and \
+             tuple structures.",
+        );
 
-                None
-            }
-        })
-        .and_then(|_| {
-            match sig.decl.output {
-                FunctionRetTy::Default(_) => Some(()),
-                FunctionRetTy::Ty(ref ty) => {
-                    cx.span_err(
-                        ty.span,
-                        "A signal can only return an empty value. Perhaps in \
-                         the future, this restriction will be removed.",
+        return vec![];
+    }
+
+    signals
+        .into_iter()
+        .flat_map(|(span, ident, inputs, options)| {
+            injects[injects.len() - 1].push(Inject {
+                ty: ty.clone(),
+                target: None,
+                fields: vec![sig_field(cx, span, ident, inputs, &options)],
+                signals: vec![Signal {
+                    span,
+                    ident,
+                    inputs: inputs.to_vec(),
+                    storage: Storage::Field,
+                    options: options.clone(),
+                }],
+            });
+
+            [Mutability::Immutable, Mutability::Mutable]
+                .iter()
+                .map(|&mutbl| {
+                    let slots = cx.expr_field_access(
+                        DUMMY_SP,
+                        cx.expr_self(DUMMY_SP),
+                        ident,
                     );
+                    let slots = match mutbl {
+                        Mutability::Immutable => {
+                            cx.expr_addr_of(DUMMY_SP, slots)
+                        },
+                        Mutability::Mutable => {
+                            cx.expr_mut_addr_of(DUMMY_SP, slots)
+                        },
+                    };
 
-                    None
-                },
-            }
+                    sig_fn(
+                        sig_accessor_ident(ident, mutbl),
+                        VisibilityKind::Inherited,
+                        sig_accessor(cx, inputs, &options, mutbl),
+                        cx.block_expr(slots),
+                    )
+                })
+                .chain(Some(sig_fn(
+                    sig_sender_ident(ident),
+                    VisibilityKind::Inherited,
+                    sig_sender(cx),
+                    cx.block_expr(cx.expr_str(
+                        DUMMY_SP,
+                        Symbol::intern(&pprust::ty_to_string(ty)),
+                    )),
+                )))
+                .flat_map(|item| SigSpan(span).fold_impl_item(item))
+                .collect::<Vec<_>>()
         })
-        .map_or_else(
-            || (sig.clone(), body.clone()),
-            |_| {
-                injects[injects.len() - 1].push((
-                ty.clone(),
-                vec![
-                    StructField {
-                        span: DUMMY_SP,
-                        ident: Some(ident),
-                        vis: Visibility {
-                            node: VisibilityKind::Public,
-                            span: DUMMY_SP,
-                        },
-                        id: DUMMY_NODE_ID,
-                        ty: cx.ty_path(Path {
-                            span: DUMMY_SP,
-                            segments: vec![
-                                PathSegment {
-                                    identifier: Ident::from_str("Vec"),
-                                    span: DUMMY_SP,
-                                    parameters: Some(P(
-                                        PathParameters::AngleBracketed(
-                                            AngleBracketedParameterData {
-                                                span: DUMMY_SP,
-                                                lifetimes: vec![],
-                                                types: vec![cx.ty_path(Path {
-                                                    span: DUMMY_SP,
-                                                    segments: vec![
-                                                        PathSegment {
-                                                            identifier: Ident::from_str("Rc"),
-                                                            span: DUMMY_SP,
-                                                            parameters: Some(P(
-                                                                PathParameters::AngleBracketed(
-                                                                    AngleBracketedParameterData {
-                                                                        span: DUMMY_SP,
-                                                                        lifetimes: vec![],
-                                                                        types: vec![cx.ty_path(Path {
-                                                                            span: DUMMY_SP,
-                                                                            segments: vec![
-                                                                                PathSegment {
-                                                                                    identifier: Ident::from_str("Fn"),
-                                                                                    span: DUMMY_SP,
-                                                                                    parameters: Some(P(
-                                                                                        PathParameters::Parenthesized(
-                                                                                            ParenthesizedParameterData {
-                                                                                                span: DUMMY_SP,
-                                                                                                inputs: sig.decl.inputs[1..].iter().map(|arg| {
-                                                                                                    arg.ty.clone()
-                                                                                                }).collect(),
-                                                                                                output: None,
-                                                                                            },
-                                                                                        ),
-                                                                                    )),
-                                                                                }
-                                                                            ],
-                                                                        })],
-                                                                        bindings: vec![],
-                                                                    },
-                                                                ),
-                                                            )),
-                                                        }
-                                                    ],
-                                                })],
-                                                bindings: vec![],
-                                            },
-                                        ),
-                                    )),
-                                },
-                            ],
-                        }),
-                        attrs: vec![],
-                    },
-                ],
-            ));
+        .collect()
+}
 
-                let mut stmts = body.stmts.clone();
+fn sig_declaration(
+    cx: &mut ExtCtxt,
+    vis: Visibility,
+    item: TraitItem,
+) -> Option<ImplItem>
+{
+    match item.node {
+        TraitItemKind::Method(sig, None) => {
+            let mut attrs = item.attrs;
 
-                stmts.push(
-                    cx.stmt_expr(
-                        cx.expr(
-                            DUMMY_SP,
-                            ExprKind::ForLoop(
-                                cx.pat_ident(DUMMY_SP, Ident::from_str("slot")),
-                                cx.expr_method_call(
-                                    DUMMY_SP,
-                                    cx.expr_field_access(
-                                        DUMMY_SP,
-                                        cx.expr_self(DUMMY_SP),
-                                        ident,
-                                    ),
-                                    Ident::from_str("iter"),
-                                    vec![],
-                                ),
-                                cx.block_expr(
-                                    cx.expr_call(
-                                        DUMMY_SP,
-                                        cx.expr_ident(
-                                            DUMMY_SP,
-                                            Ident::from_str("slot"),
-                                        ),
-                                        sig.decl.inputs[1..]
-                                            .iter()
-                                            .map(|arg| {
-                                                if let PatKind::Ident(
-                                                    _,
-                                                    ident,
-                                                    _,
-                                                ) = arg.pat.node
-                                                {
-                                                    cx.expr_ident(
-                                                        DUMMY_SP,
-                                                        ident.node,
-                                                    )
-                                                } else {
-                                                    cx.span_err(
-                                                        arg.pat.span,
-                                                        "Unexpected pattern.",
-                                                    );
-
-                                                    cx.expr_ident(
-                                                        DUMMY_SP,
-                                                        Ident::from_str(""),
-                                                    )
-                                                }
-                                            })
-                                            .collect(),
-                                    ),
-                                ),
-                                None,
-                            ),
-                        ),
-                    ),
-                );
+            if !sig_find(&attrs) {
+                let word = cx.meta_word(item.span, Symbol::intern("sig"));
+
+                attrs.push(cx.attribute(item.span, word));
+            }
 
-                (sig.clone(), cx.block(body.span, stmts))
+            attrs.push(sig_allow(cx, "dead_code"));
+
+            Some(ImplItem {
+                id: item.id,
+                ident: item.ident,
+                vis,
+                defaultness: Defaultness::Final,
+                attrs,
+                generics: item.generics,
+                node: ImplItemKind::Method(sig, cx.block(item.span, vec![])),
+                span: item.span,
+                tokens: None,
+            })
+        },
+        TraitItemKind::Method(_, Some(_)) => {
+            cx.span_err(item.span, "Signal declarations can't have a body.");
+
+            None
+        },
+        _ => {
+            cx.span_err(
+                item.span,
+                "Only signal declarations are allowed in this macro.",
+            );
+
+            None
+        },
+    }
+}
+
+fn sig_declarations(cx: &mut ExtCtxt, mac: &Mac) -> Vec<ImplItem> {
+    let tts = mac.node.stream().into_trees().collect::<Vec<_>>();
+    let mut parser = cx.new_parser_from_tts(&tts);
+    let mut items = vec![];
+
+    while parser.token != Token::Eof {
+        let item = parser
+            .parse_visibility(false)
+            .and_then(|vis| Ok((vis, parser.parse_trait_item(&mut false)?)));
+
+        match item {
+            Ok((vis, item)) => items.push((vis, item)),
+            Err(mut error) => {
+                error.emit();
+
+                break;
             },
-        )
+        }
+    }
+
+    items
+        .into_iter()
+        .filter_map(|(vis, item)| sig_declaration(cx, vis, item))
+        .collect()
 }
 
 fn sig_impl_item(
     cx: &mut ExtCtxt,
-    injects: &mut [Vec<(P<Ty>, Vec<StructField>)>],
+    injects: &mut [Vec<Inject>],
+    storage: Storage,
+    cloned: bool,
     ty: &P<Ty>,
     item: &ImplItem,
-) -> ImplItem
+) -> Vec<ImplItem>
 {
     if let ImplItemKind::Method(ref method, ref body) = item.node {
-        let (method, body) = sig_method(
+        let (method, body, items) = sig_method(
             cx,
             injects,
+            storage,
+            cloned,
             ty,
+            item.span,
             item.ident,
             &item.attrs,
             method,
             body,
         );
 
-        ImplItem {
+        let method = ImplItem {
             id: item.id,
             ident: item.ident,
             vis: item.vis.clone(),
             defaultness: item.defaultness,
-            attrs: item.attrs.clone(),
+            attrs: sig_strip(&item.attrs),
             generics: item.generics.clone(),
             node: ImplItemKind::Method(method, body),
             span: item.span,
             tokens: item.tokens.clone(),
+        };
+
+        [vec![method], items]
+            .concat()
+            .into_iter()
+            .flat_map(|method| SigSpan(item.span).fold_impl_item(method))
+            .collect()
+    } else if let ImplItemKind::Macro(ref mac) = item.node {
+        if sig_path(&mac.node.path) {
+            sig_declarations(cx, mac)
+                .iter()
+                .flat_map(|item| {
+                    sig_impl_item(cx, injects, storage, cloned, ty, item)
+                })
+                .collect()
+        } else {
+            vec![item.clone()]
         }
     } else {
-        let sig_find = item.attrs.iter().any(|attr| {
-            attr.path ==
-                Path::from_ident(attr.path.span, Ident::from_str("sig"))
-        });
-
-        if sig_find {
+        if sig_find(&item.attrs) {
             cx.span_err(
                 item.span,
                 "Using sig attribute is only allowed for methods and crates",
             );
         }
 
-        item.clone()
+        vec![ImplItem {
+            attrs: sig_strip(&item.attrs),
+            ..item.clone()
+        }]
     }
 }
 
 fn sig_impl_items(
     cx: &mut ExtCtxt,
-    injects: &mut [Vec<(P<Ty>, Vec<StructField>)>],
+    injects: &mut [Vec<Inject>],
+    storage: Storage,
+    cloned: bool,
     ty: &P<Ty>,
     items: &[ImplItem],
 ) -> Vec<ImplItem>
 {
     items
         .iter()
-        .map(|item| sig_impl_item(cx, injects, ty, item))
+        .flat_map(|item| {
+            sig_impl_item(cx, injects, storage, cloned, ty, item)
+        })
         .collect()
 }
 
 fn sig_item(
     cx: &mut ExtCtxt,
     visited: &[&Mod],
-    injects: &mut [Vec<(P<Ty>, Vec<StructField>)>],
+    injects: &mut [Vec<Inject>],
     item: &P<Item>,
 ) -> P<Item>
 {
-    let sig_find = item.attrs.iter().any(|attr| {
-        attr.path == Path::from_ident(attr.path.span, Ident::from_str("sig"))
-    });
-
-    if sig_find {
+    if sig_find(&item.attrs) {
         cx.span_err(
             item.span,
             "Using sig attribute is only allowed for methods and crates.",
@@ -451,6 +3659,42 @@ fn sig_item(
 
                     item.clone()
                 } else {
+                    let storage = sig_storage(visited, ty);
+                    let cloned = sig_cloned(visited, ty);
+                    let level = injects.len() - 1;
+                    let start = injects[level].len();
+
+                    sig_dependencies(cx, visited, ty, items);
+
+                    let items = sig_impl_items(
+                        cx,
+                        injects,
+                        storage,
+                        cloned,
+                        ty,
+                        items,
+                    );
+                    let items = match *trait_ref {
+                        Some(ref trait_ref) => {
+                            [
+                                items,
+                                sig_trait_impl_items(
+                                    cx,
+                                    visited,
+                                    injects,
+                                    storage,
+                                    ty,
+                                    &trait_ref.path,
+                                ),
+                            ].concat()
+                        },
+                        None => items,
+                    };
+
+                    for inject in &mut injects[level][start..] {
+                        inject.target = sig_target(visited, ty);
+                    }
+
                     P(Item {
                         ident: item.ident,
                         attrs: item.attrs.clone(),
@@ -462,7 +3706,7 @@ fn sig_item(
                             generics.clone(),
                             trait_ref.clone(),
                             ty.clone(),
-                            sig_impl_items(cx, injects, ty, items),
+                            items,
                         ),
                         vis: item.vis.clone(),
                         span: item.span,
@@ -470,6 +3714,35 @@ fn sig_item(
                     })
                 }
             },
+            ItemKind::Trait(
+                is_auto,
+                unsafety,
+                ref generics,
+                ref bounds,
+                ref items,
+            ) if !visited.is_empty() =>
+            {
+                P(Item {
+                    ident: item.ident,
+                    attrs: item.attrs.clone(),
+                    id: item.id,
+                    node: ItemKind::Trait(
+                        is_auto,
+                        unsafety,
+                        generics.clone(),
+                        bounds.clone(),
+                        items
+                            .iter()
+                            .flat_map(|trait_item| {
+                                sig_trait_item(cx, item.ident, trait_item)
+                            })
+                            .collect(),
+                    ),
+                    vis: item.vis.clone(),
+                    span: item.span,
+                    tokens: item.tokens.clone(),
+                })
+            },
             _ => {
                 if visited.is_empty() {
                     cx.span_err(
@@ -494,13 +3767,37 @@ fn sig(
     item: Annotatable,
 ) -> Vec<Annotatable>
 {
+    let unexpanded = Error::new(
+        item.span(),
+        "The signal isn't expanded, since only the crate with #![sig] \
+         expands signals.",
+    ).help(
+        "the output of macros is expanded after the crate, so signals can't \
+         be declared in it",
+    );
+
     vec![
         match item {
             Annotatable::Item(item) => {
+                let mut cfg = StripUnconfigured {
+                    should_test: cx.ecfg.should_test,
+                    sess: cx.parse_sess,
+                    features: cx.ecfg.features,
+                };
+                let item = item.map(|item| cfg.fold_item_simple(item));
+
                 Annotatable::Item(sig_item(cx, &[], &mut [], &item))
             },
-            Annotatable::TraitItem(item) => Annotatable::TraitItem(item),
-            Annotatable::ImplItem(item) => Annotatable::ImplItem(item),
+            Annotatable::TraitItem(item) => {
+                sig_report(cx, &[unexpanded]);
+
+                Annotatable::TraitItem(item)
+            },
+            Annotatable::ImplItem(item) => {
+                sig_report(cx, &[unexpanded]);
+
+                Annotatable::ImplItem(item)
+            },
         },
     ]
 }
@@ -0,0 +1,114 @@
+// Copyright 2018 Sergey Sherkunov <leinlawun@leinlawun.org>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(plugin)]
+#![plugin(sig)]
+#![sig]
+
+extern crate sig_runtime;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+struct Person {
+    #[sig(notify)]
+    first: String,
+    #[sig(notify)]
+    last: String,
+}
+
+impl Person {
+    #[sig(computed(first, last))]
+    fn full_name(&self) -> String {
+        format!("{} {}", self.first, self.last)
+    }
+}
+
+struct Account {
+    #[sig(notify)]
+    balance: u32,
+}
+
+mod report {
+    impl super::Account {
+        #[sig(computed(balance))]
+        pub fn summary(&self) -> String {
+            format!("Balance: {}", self.balance)
+        }
+    }
+}
+
+fn person() -> (Person, Rc<RefCell<Vec<String>>>) {
+    let mut person = Person::new("John".to_string(), "Smith".to_string());
+    let names = Rc::new(RefCell::new(vec![]));
+    let renamed = names.clone();
+
+    person.connect_full_name_changed(Box::new(move |name: &String| {
+        renamed.borrow_mut().push(name.clone())
+    }));
+
+    (person, names)
+}
+
+#[test]
+fn first_fill() {
+    let (mut person, names) = person();
+
+    person.set_first("Jane".to_string());
+
+    assert!(names.borrow().is_empty());
+    assert_eq!(person.full_name(), "Jane Smith");
+}
+
+#[test]
+fn changed() {
+    let (mut person, names) = person();
+
+    person.full_name();
+    person.set_first("Jane".to_string());
+    person.set_last("Doe".to_string());
+
+    assert_eq!(*names.borrow(), ["Jane Smith", "Jane Doe"]);
+}
+
+#[test]
+fn unchanged() {
+    let (mut person, names) = person();
+
+    person.full_name();
+    person.set_first("John".to_string());
+    person.set_last("Smith".to_string());
+
+    assert!(names.borrow().is_empty());
+}
+
+#[test]
+fn dependency_signal() {
+    let (mut person, names) = person();
+
+    person.full_name();
+    person.first = "Jane".to_string();
+    person.emit_first_changed(&"John".to_string(), &"Jane".to_string());
+
+    assert_eq!(*names.borrow(), ["Jane Smith"]);
+}
+
+#[test]
+fn other_module() {
+    let mut account = Account::new(1);
+    let summaries = Rc::new(RefCell::new(vec![]));
+    let changed = summaries.clone();
+
+    account.connect_summary_changed(Box::new(move |summary: &String| {
+        changed.borrow_mut().push(summary.clone())
+    }));
+    account.summary();
+    account.set_balance(2);
+
+    assert_eq!(*summaries.borrow(), ["Balance: 2"]);
+}
@@ -0,0 +1,122 @@
+// Copyright 2018 Sergey Sherkunov <leinlawun@leinlawun.org>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(plugin)]
+#![plugin(sig)]
+#![sig]
+
+extern crate sig_runtime;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use sig_runtime::{DynError, DynSignals};
+
+#[derive(Default)]
+struct Greeter;
+
+impl Greeter {
+    sig! {
+        #[sig(dynamic, emit = "pub")]
+        fn greeted(&self, name: &str, times: u32);
+
+        #[sig(dynamic)]
+        fn waved(&self);
+
+        #[sig(dynamic, vis = "pub(crate)")]
+        fn left(&self);
+
+        #[sig]
+        fn smiled(&self);
+    }
+}
+
+#[test]
+fn emit() {
+    let mut greeter = Greeter::default();
+    let greetings = Rc::new(RefCell::new(vec![]));
+    let greeted = greetings.clone();
+
+    greeter
+        .connect_dyn(
+            "greeted",
+            Box::new(move |args| {
+                greeted.borrow_mut().push((
+                    args[0].downcast_ref::<String>().cloned(),
+                    args[1].downcast_ref::<u32>().cloned(),
+                ))
+            }),
+        )
+        .unwrap();
+
+    assert_eq!(greeter.emit_dyn("greeted", &[&"Bob", &2u32]), Ok(()));
+    assert_eq!(
+        *greetings.borrow(),
+        [(Some("Bob".to_owned()), Some(2))],
+    );
+}
+
+#[test]
+fn unknown() {
+    assert_eq!(
+        Greeter::default().emit_dyn("frowned", &[]),
+        Err(DynError::UnknownSignal("frowned".to_owned())),
+    );
+}
+
+#[test]
+fn not_dynamic() {
+    assert_eq!(
+        Greeter::default().emit_dyn("smiled", &[]),
+        Err(DynError::NotDynamic("smiled")),
+    );
+}
+
+#[test]
+fn private() {
+    assert_eq!(
+        Greeter::default().emit_dyn("waved", &[]),
+        Err(DynError::Private("waved")),
+    );
+    assert_eq!(
+        Greeter::default().connect_dyn("left", Box::new(|_| {})),
+        Err(DynError::Private("left")),
+    );
+}
+
+#[test]
+fn argument_count() {
+    assert_eq!(
+        Greeter::default().emit_dyn("greeted", &[&"Bob"]),
+        Err(DynError::ArgumentCount {
+            signal: "greeted",
+            expected: 2,
+            found: 1,
+        }),
+    );
+}
+
+#[test]
+fn argument_type() {
+    assert_eq!(
+        Greeter::default().emit_dyn("greeted", &[&"Bob", &2i64]),
+        Err(DynError::ArgumentType {
+            signal: "greeted",
+            index: 1,
+            expected: "u32",
+        }),
+    );
+    assert_eq!(
+        Greeter::default().emit_dyn("greeted", &[&2u32, &2u32]),
+        Err(DynError::ArgumentType {
+            signal: "greeted",
+            index: 0,
+            expected: "&str",
+        }),
+    );
+}
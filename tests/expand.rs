@@ -0,0 +1,91 @@
+// Copyright 2018 Sergey Sherkunov <leinlawun@leinlawun.org>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn expand(path: &Path) -> String {
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_owned());
+    let output = Command::new(rustc)
+        .args(&["-Z", "unpretty=expanded", "--crate-type", "lib"])
+        .args(&["-L", "target/debug", "-L", "target/debug/deps"])
+        .arg(path)
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "{} doesn't expand:\n{}",
+        path.display(),
+        String::from_utf8_lossy(&output.stderr),
+    );
+
+    String::from_utf8(output.stdout).unwrap()
+}
+
+fn read(path: &Path) -> io::Result<String> {
+    let mut content = String::new();
+
+    File::open(path)?.read_to_string(&mut content)?;
+
+    Ok(content)
+}
+
+fn sources() -> Vec<PathBuf> {
+    let mut sources = fs::read_dir("tests/expand")
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| {
+            path.extension().map_or(false, |extension| extension == "rs") &&
+                !path.to_string_lossy().ends_with(".expanded.rs")
+        })
+        .collect::<Vec<_>>();
+
+    sources.sort();
+
+    sources
+}
+
+/// Compares the expansion of every file in `tests/expand` with the
+/// `.expanded.rs` snapshot next to it. A missing snapshot is written and
+/// reported, and `SIG_RS_BLESS=1` rewrites all of them.
+#[test]
+fn expand_snapshots() {
+    let bless = env::var_os("SIG_RS_BLESS").is_some();
+    let mut failed = vec![];
+
+    for source in sources() {
+        let expanded = expand(&source);
+        let snapshot = source.with_extension("expanded.rs");
+
+        match read(&snapshot) {
+            Ok(ref content) if content == &expanded => {},
+            Ok(_) if !bless => failed.push(format!(
+                "{} differs from the expansion",
+                snapshot.display(),
+            )),
+            result => {
+                fs::write(&snapshot, &expanded).unwrap();
+
+                if result.is_err() && !bless {
+                    failed.push(format!("{} was written", snapshot.display()));
+                }
+            },
+        }
+    }
+
+    assert!(
+        failed.is_empty(),
+        "{}\nreview the snapshots and rerun with SIG_RS_BLESS=1 to accept \
+         the changes",
+        failed.join("\n"),
+    );
+}
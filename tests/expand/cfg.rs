@@ -0,0 +1,47 @@
+// Copyright 2018 Sergey Sherkunov <leinlawun@leinlawun.org>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(plugin)]
+#![plugin(sig)]
+#![sig]
+
+extern crate sig_runtime;
+
+struct Window;
+
+impl Window {
+    #[cfg_attr(all(), sig)]
+    pub fn shown(&self) {}
+
+    #[cfg_attr(any(), sig)]
+    pub fn hidden(&self) {}
+
+    #[cfg(any())]
+    #[sig]
+    pub fn closed(&self) {}
+}
+
+#[cfg(all())]
+mod platform {
+    pub struct Handle;
+
+    impl Handle {
+        #[sig]
+        pub fn released(&self) {}
+    }
+}
+
+#[cfg(any())]
+mod platform {
+    pub struct Handle;
+
+    impl Handle {
+        #[sig]
+        pub fn destroyed(&self) {}
+    }
+}
@@ -0,0 +1,102 @@
+#![feature(prelude_import)]
+#![no_std]
+// Copyright 2018 Sergey Sherkunov <leinlawun@leinlawun.org>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(plugin)]
+#![plugin(sig)]
+#[prelude_import]
+use std::prelude::v1::*;
+#[macro_use]
+extern crate std;
+
+extern crate sig_runtime;
+
+#[structural_match]
+#[rustc_copy_clone_marker]
+enum State { Idle, Running, }
+#[automatically_derived]
+#[allow(unused_qualifications)]
+impl ::std::clone::Clone for State {
+    #[inline]
+    fn clone(&self) -> State { { *self } }
+}
+#[automatically_derived]
+#[allow(unused_qualifications)]
+impl ::std::marker::Copy for State { }
+#[automatically_derived]
+#[allow(unused_qualifications)]
+impl ::std::cmp::PartialEq for State {
+    #[inline]
+    fn eq(&self, __arg_0: &State) -> bool {
+        {
+            let __self_vi =
+                unsafe { ::std::intrinsics::discriminant_value(&*self) } as
+                    isize;
+            let __arg_1_vi =
+                unsafe { ::std::intrinsics::discriminant_value(&*__arg_0) } as
+                    isize;
+            if true && __self_vi == __arg_1_vi {
+                match (&*self, &*__arg_0) { _ => true, }
+            } else { false }
+        }
+    }
+}
+#[automatically_derived]
+#[allow(unused_qualifications)]
+impl ::std::cmp::Eq for State {
+    #[inline]
+    #[doc(hidden)]
+    fn assert_receiver_is_total_eq(&self) -> () { { } }
+}
+#[automatically_derived]
+#[allow(unused_qualifications)]
+impl ::std::hash::Hash for State {
+    fn hash<__H: ::std::hash::Hasher>(&self, __arg_0: &mut __H) -> () {
+        match (&*self,) {
+            _ => {
+                ::std::hash::Hash::hash(&unsafe {
+                                             ::std::intrinsics::discriminant_value(self)
+                                         }, __arg_0)
+            }
+        }
+    }
+}
+
+impl State {
+    pub fn entered(&self, from: State) { self.emit_entered(from); }
+    #[allow(dead_code)]
+    pub fn connect_entered(&self, slot: ::std::boxed::Box<Fn(State)>) {
+        ::sig_runtime::connect::<State,
+                                 Fn(State)>("entered", &self,
+                                            ::std::rc::Rc::from(slot));
+    }
+    #[allow(dead_code)]
+    pub fn disconnect_entered(&self) {
+        ::sig_runtime::disconnect::<State, Fn(State)>("entered", &self);
+    }
+    #[allow(dead_code)]
+    fn emit_entered(&self, from: State) {
+        ::sig_runtime::Emit::emit(&::sig_runtime::keyed_slots::<State,
+                                                                Fn(State)>("entered",
+                                                                           &self),
+                                  "entered", "State",
+                                  ::std::option::Option::None,
+                                  |slot| slot(from));
+    }
+}
+impl ::sig_runtime::HasSignals for State {
+    fn signals(&self) -> ::std::vec::Vec<::sig_runtime::SignalMeta> {
+        <[_]>::into_vec(box
+                            [::sig_runtime::SignalMeta::new("entered",
+                                                            &["State"],
+                                                            ::sig_runtime::slots::<State,
+                                                                                   Fn(State)>("entered",
+                                                                                              &self).len())])
+    }
+}
@@ -0,0 +1,93 @@
+#![feature(prelude_import)]
+#![no_std]
+// Copyright 2018 Sergey Sherkunov <leinlawun@leinlawun.org>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(plugin)]
+#![plugin(sig)]
+#[prelude_import]
+use std::prelude::v1::*;
+#[macro_use]
+extern crate std;
+
+extern crate sig_runtime;
+
+mod platform {
+
+
+    pub struct Handle {
+        released: ::sig_runtime::Slots<Fn(), ::sig_runtime::Disconnect>,
+    }
+    impl Handle {
+        pub fn released(&self) { self.emit_released(); }
+        #[allow(dead_code)]
+        pub fn connect_released(&mut self, slot: ::std::boxed::Box<Fn()>) {
+            self.released.connect(slot);
+        }
+        #[allow(dead_code)]
+        pub fn disconnect_released(&mut self) {
+            self.released.disconnect_all();
+        }
+        #[allow(dead_code)]
+        fn emit_released(&self) {
+            ::sig_runtime::Emit::emit(&self.released, "released", "Handle",
+                                      ::std::option::Option::None,
+                                      |slot| slot());
+        }
+    }
+    impl ::std::default::Default for Handle {
+        fn default() -> Handle {
+            Handle{released: ::std::default::Default::default(),}
+        }
+    }
+    impl Handle {
+        #[allow(dead_code)]
+        pub fn new() -> Handle {
+            Handle{released: ::std::default::Default::default(),}
+        }
+    }
+    impl ::sig_runtime::HasSignals for Handle {
+        fn signals(&self) -> ::std::vec::Vec<::sig_runtime::SignalMeta> {
+            <[_]>::into_vec(box
+                                [::sig_runtime::SignalMeta::new("released",
+                                                                &[],
+                                                                self.released.len())])
+        }
+    }
+}
+struct Window {
+    handle: platform::Handle,
+    shown: ::sig_runtime::Slots<Fn(), ::sig_runtime::Disconnect>,
+}
+impl Window {
+    pub fn shown(&self) { self.emit_shown(); }
+    #[allow(dead_code)]
+    pub fn connect_shown(&mut self, slot: ::std::boxed::Box<Fn()>) {
+        self.shown.connect(slot);
+    }
+    #[allow(dead_code)]
+    pub fn disconnect_shown(&mut self) { self.shown.disconnect_all(); }
+    #[allow(dead_code)]
+    fn emit_shown(&self) {
+        ::sig_runtime::Emit::emit(&self.shown, "shown", "Window",
+                                  ::std::option::Option::None, |slot| slot());
+    }
+}
+impl Window {
+    #[allow(dead_code)]
+    fn new(handle: platform::Handle) -> Window {
+        Window{handle: handle, shown: ::std::default::Default::default(),}
+    }
+}
+impl ::sig_runtime::HasSignals for Window {
+    fn signals(&self) -> ::std::vec::Vec<::sig_runtime::SignalMeta> {
+        <[_]>::into_vec(box
+                            [::sig_runtime::SignalMeta::new("shown", &[],
+                                                            self.shown.len())])
+    }
+}
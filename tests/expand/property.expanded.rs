@@ -0,0 +1,161 @@
+#![feature(prelude_import)]
+#![no_std]
+// Copyright 2018 Sergey Sherkunov <leinlawun@leinlawun.org>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(plugin)]
+#![plugin(sig)]
+#[prelude_import]
+use std::prelude::v1::*;
+#[macro_use]
+extern crate std;
+
+extern crate sig_runtime;
+
+struct Rectangle {
+    width: u32,
+    height: u32,
+    width_changed: ::sig_runtime::Slots<Fn(&u32, &u32),
+                                        ::sig_runtime::Disconnect>,
+    height_changed: ::sig_runtime::Slots<Fn(&u32, &u32),
+                                         ::sig_runtime::Disconnect>,
+
+    area: ::sig_runtime::Computed<u32>,
+    area_changed: ::sig_runtime::Slots<Fn(&u32), ::sig_runtime::Disconnect>,
+}
+#[automatically_derived]
+#[allow(unused_qualifications)]
+impl ::std::default::Default for Rectangle {
+    #[inline]
+    fn default() -> Rectangle {
+        Rectangle{width: ::std::default::Default::default(),
+                  height: ::std::default::Default::default(),
+                  width_changed: ::std::default::Default::default(),
+                  height_changed: ::std::default::Default::default(),
+                  area: ::std::default::Default::default(),
+                  area_changed: ::std::default::Default::default(),}
+    }
+}
+impl Rectangle {
+    pub fn area(&self) -> u32 {
+        self.area.get(|| { self.width * self.height })
+    }
+    #[allow(dead_code)]
+    pub(crate) fn update_area(&self) {
+        let old = self.area.invalidate();
+        if !self.area_changed.is_empty() {
+            {
+                let new = self.area();
+                if old.map_or(false, |old| old != new) {
+                    { self.emit_area_changed(&new); }
+                }
+            }
+        }
+    }
+    #[allow(dead_code)]
+    pub fn connect_area_changed(&mut self,
+                                slot: ::std::boxed::Box<Fn(&u32)>) {
+        self.area_changed.connect(slot);
+    }
+    #[allow(dead_code)]
+    pub fn disconnect_area_changed(&mut self) {
+        self.area_changed.disconnect_all();
+    }
+    #[allow(dead_code)]
+    fn emit_area_changed(&self, value: &u32) {
+        ::sig_runtime::Emit::emit(&self.area_changed, "area_changed",
+                                  "Rectangle", ::std::option::Option::None,
+                                  |slot| slot(value));
+    }
+}
+impl Rectangle {
+    #[allow(dead_code)]
+    fn new(width: u32, height: u32) -> Rectangle {
+        Rectangle{width: width,
+                  height: height,
+                  width_changed: ::std::default::Default::default(),
+                  height_changed: ::std::default::Default::default(),
+                  area: ::std::default::Default::default(),
+                  area_changed: ::std::default::Default::default(),}
+    }
+}
+#[allow(dead_code)]
+impl Rectangle {
+    fn width(&self) -> &u32 { &self.width }
+    fn set_width(&mut self, value: u32) {
+        if self.width != value {
+            {
+                let old = ::std::mem::replace(&mut self.width, value);
+                self.emit_width_changed(&old, &self.width);
+            }
+        }
+    }
+    fn width_property() -> ::sig_runtime::Property<Self, u32> {
+        ::sig_runtime::Property::new(Self::width, Self::set_width,
+                                     Self::connect_width_changed)
+    }
+    #[allow(dead_code)]
+    pub fn connect_width_changed(&mut self,
+                                 slot: ::std::boxed::Box<Fn(&u32, &u32)>) {
+        self.width_changed.connect(slot);
+    }
+    #[allow(dead_code)]
+    pub fn disconnect_width_changed(&mut self) {
+        self.width_changed.disconnect_all();
+    }
+    #[allow(dead_code)]
+    fn emit_width_changed(&self, old: &u32, new: &u32) {
+        self.update_area();
+        ::sig_runtime::Emit::emit(&self.width_changed, "width_changed",
+                                  "Rectangle", ::std::option::Option::None,
+                                  |slot| slot(old, new));
+    }
+    fn height(&self) -> &u32 { &self.height }
+    fn set_height(&mut self, value: u32) {
+        if self.height != value {
+            {
+                let old = ::std::mem::replace(&mut self.height, value);
+                self.emit_height_changed(&old, &self.height);
+            }
+        }
+    }
+    fn height_property() -> ::sig_runtime::Property<Self, u32> {
+        ::sig_runtime::Property::new(Self::height, Self::set_height,
+                                     Self::connect_height_changed)
+    }
+    #[allow(dead_code)]
+    pub fn connect_height_changed(&mut self,
+                                  slot: ::std::boxed::Box<Fn(&u32, &u32)>) {
+        self.height_changed.connect(slot);
+    }
+    #[allow(dead_code)]
+    pub fn disconnect_height_changed(&mut self) {
+        self.height_changed.disconnect_all();
+    }
+    #[allow(dead_code)]
+    fn emit_height_changed(&self, old: &u32, new: &u32) {
+        self.update_area();
+        ::sig_runtime::Emit::emit(&self.height_changed, "height_changed",
+                                  "Rectangle", ::std::option::Option::None,
+                                  |slot| slot(old, new));
+    }
+}
+impl ::sig_runtime::HasSignals for Rectangle {
+    fn signals(&self) -> ::std::vec::Vec<::sig_runtime::SignalMeta> {
+        <[_]>::into_vec(box
+                            [::sig_runtime::SignalMeta::new("width_changed",
+                                                            &["&u32", "&u32"],
+                                                            self.width_changed.len()),
+                             ::sig_runtime::SignalMeta::new("height_changed",
+                                                            &["&u32", "&u32"],
+                                                            self.height_changed.len()),
+                             ::sig_runtime::SignalMeta::new("area_changed",
+                                                            &["&u32"],
+                                                            self.area_changed.len())])
+    }
+}
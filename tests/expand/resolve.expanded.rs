@@ -0,0 +1,263 @@
+#![feature(prelude_import)]
+#![no_std]
+// Copyright 2018 Sergey Sherkunov <leinlawun@leinlawun.org>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(plugin)]
+#![plugin(sig)]
+#[prelude_import]
+use std::prelude::v1::*;
+#[macro_use]
+extern crate std;
+
+extern crate sig_runtime;
+
+#[structural_match]
+#[rustc_copy_clone_marker]
+enum State { Idle, Running, }
+#[automatically_derived]
+#[allow(unused_qualifications)]
+impl ::std::clone::Clone for State {
+    #[inline]
+    fn clone(&self) -> State { { *self } }
+}
+#[automatically_derived]
+#[allow(unused_qualifications)]
+impl ::std::marker::Copy for State { }
+#[automatically_derived]
+#[allow(unused_qualifications)]
+impl ::std::cmp::PartialEq for State {
+    #[inline]
+    fn eq(&self, __arg_0: &State) -> bool {
+        {
+            let __self_vi =
+                unsafe { ::std::intrinsics::discriminant_value(&*self) } as
+                    isize;
+            let __arg_1_vi =
+                unsafe { ::std::intrinsics::discriminant_value(&*__arg_0) } as
+                    isize;
+            if true && __self_vi == __arg_1_vi {
+                match (&*self, &*__arg_0) { _ => true, }
+            } else { false }
+        }
+    }
+}
+#[automatically_derived]
+#[allow(unused_qualifications)]
+impl ::std::cmp::Eq for State {
+    #[inline]
+    #[doc(hidden)]
+    fn assert_receiver_is_total_eq(&self) -> () { { } }
+}
+#[automatically_derived]
+#[allow(unused_qualifications)]
+impl ::std::hash::Hash for State {
+    fn hash<__H: ::std::hash::Hasher>(&self, __arg_0: &mut __H) -> () {
+        match (&*self,) {
+            _ => {
+                ::std::hash::Hash::hash(&unsafe {
+                                             ::std::intrinsics::discriminant_value(self)
+                                         }, __arg_0)
+            }
+        }
+    }
+}
+
+struct Person {
+    first: String,
+    first_changed: ::sig_runtime::Slots<Fn(&String, &String),
+                                        ::sig_runtime::Disconnect>,
+
+
+    greeting: ::sig_runtime::Computed<String>,
+    greeting_changed: ::sig_runtime::Slots<Fn(&String),
+                                           ::sig_runtime::Disconnect>,
+}
+mod machine {
+    impl super::State {
+        pub fn entered(&self) { self.emit_entered(); }
+        #[allow(dead_code)]
+        pub fn connect_entered(&self, slot: ::std::boxed::Box<Fn()>) {
+            ::sig_runtime::connect::<super::State,
+                                     Fn()>("entered", &self,
+                                           ::std::rc::Rc::from(slot));
+        }
+        #[allow(dead_code)]
+        pub fn disconnect_entered(&self) {
+            ::sig_runtime::disconnect::<super::State, Fn()>("entered", &self);
+        }
+        #[allow(dead_code)]
+        fn emit_entered(&self) {
+            ::sig_runtime::Emit::emit(&::sig_runtime::keyed_slots::<super::State,
+                                                                    Fn()>("entered",
+                                                                          &self),
+                                      "entered", "super::State",
+                                      ::std::option::Option::None,
+                                      |slot| slot());
+        }
+    }
+    impl super::Person {
+        pub fn greeting(&self) -> String {
+            self.greeting.get(||
+                                  {
+
+
+                                      ::fmt::format(::std::fmt::Arguments::new_v1_formatted(&["Hello, "],
+                                                                                            &match (&self.first,)
+                                                                                                 {
+                                                                                                 (__arg0,)
+                                                                                                 =>
+                                                                                                 [::std::fmt::ArgumentV1::new(__arg0,
+                                                                                                                              ::std::fmt::Display::fmt)],
+                                                                                             },
+                                                                                            &[::std::fmt::rt::v1::Argument{position:
+                                                                                                                               ::std::fmt::rt::v1::Position::At(0usize),
+                                                                                                                           format:
+                                                                                                                               ::std::fmt::rt::v1::FormatSpec{fill:
+                                                                                                                                                                  ' ',
+                                                                                                                                                              align:
+                                                                                                                                                                  ::std::fmt::rt::v1::Alignment::Unknown,
+                                                                                                                                                              flags:
+                                                                                                                                                                  0u32,
+                                                                                                                                                              precision:
+                                                                                                                                                                  ::std::fmt::rt::v1::Count::Implied,
+                                                                                                                                                              width:
+                                                                                                                                                                  ::std::fmt::rt::v1::Count::Implied,},}]))
+                                  })
+        }
+        #[allow(dead_code)]
+        pub(crate) fn update_greeting(&self) {
+            let old = self.greeting.invalidate();
+            if !self.greeting_changed.is_empty() {
+                {
+                    let new = self.greeting();
+                    if old.map_or(false, |old| old != new) {
+                        { self.emit_greeting_changed(&new); }
+                    }
+                }
+            }
+        }
+        #[allow(dead_code)]
+        pub fn connect_greeting_changed(&mut self,
+                                        slot:
+                                            ::std::boxed::Box<Fn(&String)>) {
+            self.greeting_changed.connect(slot);
+        }
+        #[allow(dead_code)]
+        pub fn disconnect_greeting_changed(&mut self) {
+            self.greeting_changed.disconnect_all();
+        }
+        #[allow(dead_code)]
+        fn emit_greeting_changed(&self, value: &String) {
+            ::sig_runtime::Emit::emit(&self.greeting_changed,
+                                      "greeting_changed", "super::Person",
+                                      ::std::option::Option::None,
+                                      |slot| slot(value));
+        }
+    }
+    pub struct Person {
+        moved: ::sig_runtime::Slots<Fn(), ::sig_runtime::Disconnect>,
+    }
+    impl Person {
+        pub fn moved(&self) { self.emit_moved(); }
+        #[allow(dead_code)]
+        pub fn connect_moved(&mut self, slot: ::std::boxed::Box<Fn()>) {
+            self.moved.connect(slot);
+        }
+        #[allow(dead_code)]
+        pub fn disconnect_moved(&mut self) { self.moved.disconnect_all(); }
+        #[allow(dead_code)]
+        fn emit_moved(&self) {
+            ::sig_runtime::Emit::emit(&self.moved, "moved", "Person",
+                                      ::std::option::Option::None,
+                                      |slot| slot());
+        }
+    }
+    impl ::std::default::Default for Person {
+        fn default() -> Person {
+            Person{moved: ::std::default::Default::default(),}
+        }
+    }
+    impl Person {
+        #[allow(dead_code)]
+        pub fn new() -> Person {
+            Person{moved: ::std::default::Default::default(),}
+        }
+    }
+    impl ::sig_runtime::HasSignals for Person {
+        fn signals(&self) -> ::std::vec::Vec<::sig_runtime::SignalMeta> {
+            <[_]>::into_vec(box
+                                [::sig_runtime::SignalMeta::new("moved", &[],
+                                                                self.moved.len())])
+        }
+    }
+}
+impl Person {
+    #[allow(dead_code)]
+    fn new(first: String) -> Person {
+        Person{first: first,
+               first_changed: ::std::default::Default::default(),
+               greeting: ::std::default::Default::default(),
+               greeting_changed: ::std::default::Default::default(),}
+    }
+}
+#[allow(dead_code)]
+impl Person {
+    fn first(&self) -> &String { &self.first }
+    fn set_first(&mut self, value: String) {
+        if self.first != value {
+            {
+                let old = ::std::mem::replace(&mut self.first, value);
+                self.emit_first_changed(&old, &self.first);
+            }
+        }
+    }
+    fn first_property() -> ::sig_runtime::Property<Self, String> {
+        ::sig_runtime::Property::new(Self::first, Self::set_first,
+                                     Self::connect_first_changed)
+    }
+    #[allow(dead_code)]
+    pub fn connect_first_changed(&mut self,
+                                 slot:
+                                     ::std::boxed::Box<Fn(&String,
+                                                          &String)>) {
+        self.first_changed.connect(slot);
+    }
+    #[allow(dead_code)]
+    pub fn disconnect_first_changed(&mut self) {
+        self.first_changed.disconnect_all();
+    }
+    #[allow(dead_code)]
+    fn emit_first_changed(&self, old: &String, new: &String) {
+        self.update_greeting();
+        ::sig_runtime::Emit::emit(&self.first_changed, "first_changed",
+                                  "Person", ::std::option::Option::None,
+                                  |slot| slot(old, new));
+    }
+}
+impl ::sig_runtime::HasSignals for State {
+    fn signals(&self) -> ::std::vec::Vec<::sig_runtime::SignalMeta> {
+        <[_]>::into_vec(box
+                            [::sig_runtime::SignalMeta::new("entered", &[],
+                                                            ::sig_runtime::slots::<State,
+                                                                                   Fn()>("entered",
+                                                                                         &self).len())])
+    }
+}
+impl ::sig_runtime::HasSignals for Person {
+    fn signals(&self) -> ::std::vec::Vec<::sig_runtime::SignalMeta> {
+        <[_]>::into_vec(box
+                            [::sig_runtime::SignalMeta::new("first_changed",
+                                                            &["&String",
+                                                              "&String"],
+                                                            self.first_changed.len()),
+                             ::sig_runtime::SignalMeta::new("greeting_changed",
+                                                            &["&String"],
+                                                            self.greeting_changed.len())])
+    }
+}
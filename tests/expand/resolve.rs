@@ -0,0 +1,45 @@
+// Copyright 2018 Sergey Sherkunov <leinlawun@leinlawun.org>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(plugin)]
+#![plugin(sig)]
+#![sig]
+
+extern crate sig_runtime;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum State {
+    Idle,
+    Running,
+}
+
+struct Person {
+    #[sig(notify)]
+    first: String,
+}
+
+mod machine {
+    impl super::State {
+        #[sig]
+        pub fn entered(&self) {}
+    }
+
+    impl super::Person {
+        #[sig(computed(first))]
+        pub fn greeting(&self) -> String {
+            format!("Hello, {}", self.first)
+        }
+    }
+
+    pub struct Person;
+
+    impl Person {
+        #[sig]
+        pub fn moved(&self) {}
+    }
+}
@@ -0,0 +1,72 @@
+#![feature(prelude_import)]
+#![no_std]
+// Copyright 2018 Sergey Sherkunov <leinlawun@leinlawun.org>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(plugin)]
+#![plugin(sig)]
+#[prelude_import]
+use std::prelude::v1::*;
+#[macro_use]
+extern crate std;
+
+extern crate sig_runtime;
+
+struct Person {
+    name: String,
+
+    greeted: ::sig_runtime::Slots<Fn(&str), ::sig_runtime::Disconnect>,
+
+    left: ::sig_runtime::Slots<Fn(), ::sig_runtime::Disconnect>,
+}
+impl Person {
+    pub fn greeted(&self, name: &str) { self.emit_greeted(name); }
+    #[allow(dead_code)]
+    pub fn connect_greeted(&mut self, slot: ::std::boxed::Box<Fn(&str)>) {
+        self.greeted.connect(slot);
+    }
+    #[allow(dead_code)]
+    pub fn disconnect_greeted(&mut self) { self.greeted.disconnect_all(); }
+    #[allow(dead_code)]
+    fn emit_greeted(&self, name: &str) {
+        ::sig_runtime::Emit::emit(&self.greeted, "greeted", "Person",
+                                  ::std::option::Option::None,
+                                  |slot| slot(name));
+    }
+    #[allow(dead_code)]
+    pub fn left(&self) { self.emit_left(); }
+    #[allow(dead_code)]
+    pub fn connect_left(&mut self, slot: ::std::boxed::Box<Fn()>) {
+        self.left.connect(slot);
+    }
+    #[allow(dead_code)]
+    pub fn disconnect_left(&mut self) { self.left.disconnect_all(); }
+    #[allow(dead_code)]
+    fn emit_left(&self) {
+        ::sig_runtime::Emit::emit(&self.left, "left", "Person",
+                                  ::std::option::Option::None, |slot| slot());
+    }
+}
+impl Person {
+    #[allow(dead_code)]
+    fn new(name: String) -> Person {
+        Person{name: name,
+               greeted: ::std::default::Default::default(),
+               left: ::std::default::Default::default(),}
+    }
+}
+impl ::sig_runtime::HasSignals for Person {
+    fn signals(&self) -> ::std::vec::Vec<::sig_runtime::SignalMeta> {
+        <[_]>::into_vec(box
+                            [::sig_runtime::SignalMeta::new("greeted",
+                                                            &["&str"],
+                                                            self.greeted.len()),
+                             ::sig_runtime::SignalMeta::new("left", &[],
+                                                            self.left.len())])
+    }
+}
@@ -0,0 +1,79 @@
+#![feature(prelude_import)]
+#![no_std]
+// Copyright 2018 Sergey Sherkunov <leinlawun@leinlawun.org>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(plugin)]
+#![plugin(sig)]
+#[prelude_import]
+use std::prelude::v1::*;
+#[macro_use]
+extern crate std;
+
+extern crate sig_runtime;
+
+mod widgets {
+    pub trait Widget {
+        #[doc(hidden)]
+        fn clicked_slots(&self)
+        -> &::sig_runtime::Slots<Fn()>;
+        #[doc(hidden)]
+        fn clicked_slots_mut(&mut self)
+        -> &mut ::sig_runtime::Slots<Fn()>;
+        #[doc(hidden)]
+        fn clicked_sender(&self)
+        -> &'static str;
+        #[allow(dead_code)]
+        fn connect_clicked(&mut self, slot: ::std::boxed::Box<Fn()>) {
+            self.clicked_slots_mut().connect(::std::rc::Rc::from(slot));
+        }
+        #[allow(dead_code)]
+        fn disconnect_clicked(&mut self) {
+            self.clicked_slots_mut().disconnect_all();
+        }
+        #[allow(dead_code)]
+        #[doc(hidden)]
+        fn emit_clicked(&self) {
+            ::sig_runtime::Emit::emit(self.clicked_slots(), "clicked",
+                                      self.clicked_sender(),
+                                      ::std::option::Option::None,
+                                      |slot| slot());
+        }
+        fn clicked(&self) { self.emit_clicked(); }
+    }
+}
+
+struct Button {
+    clicked: ::sig_runtime::Slots<Fn()>,
+}
+
+impl widgets::Widget for Button {
+    fn clicked_slots(&self) -> &::sig_runtime::Slots<Fn()> { &self.clicked }
+    fn clicked_slots_mut(&mut self) -> &mut ::sig_runtime::Slots<Fn()> {
+        &mut self.clicked
+    }
+    fn clicked_sender(&self) -> &'static str { "Button" }
+}
+impl ::std::default::Default for Button {
+    fn default() -> Button {
+        Button{clicked: ::std::default::Default::default(),}
+    }
+}
+impl Button {
+    #[allow(dead_code)]
+    fn new() -> Button {
+        Button{clicked: ::std::default::Default::default(),}
+    }
+}
+impl ::sig_runtime::HasSignals for Button {
+    fn signals(&self) -> ::std::vec::Vec<::sig_runtime::SignalMeta> {
+        <[_]>::into_vec(box
+                            [::sig_runtime::SignalMeta::new("clicked", &[],
+                                                            self.clicked.len())])
+    }
+}
@@ -0,0 +1,78 @@
+// Copyright 2018 Sergey Sherkunov <leinlawun@leinlawun.org>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(plugin)]
+#![plugin(sig)]
+#![sig]
+
+extern crate sig_runtime;
+
+use std::cell::{Cell, RefCell};
+use std::panic::catch_unwind;
+use std::rc::Rc;
+
+use sig_runtime::take_panics;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum Light {
+    Red,
+    Green,
+}
+
+impl Light {
+    sig! {
+        #[sig(mutable, panic = "continue")]
+        fn switched(&self);
+
+        #[sig(mutable)]
+        fn blinked(&self);
+    }
+}
+
+const MESSAGE: &str =
+    "a slot of the signal `switched` was called while it was already running";
+
+#[test]
+fn reentrant_slot() {
+    Light::Red.connect_switched(Box::new(RefCell::new(|| {
+        Light::Red.emit_switched()
+    })));
+    take_panics();
+    Light::Red.emit_switched();
+
+    let panics = take_panics();
+
+    assert_eq!(panics.len(), 1);
+    assert_eq!(panics[0].signal(), "switched");
+    assert_eq!(panics[0].index(), 0);
+    assert_eq!(panics[0].message(), Some(MESSAGE));
+}
+
+#[test]
+fn reentrant_slot_unwinds() {
+    let reenter = Rc::new(Cell::new(true));
+    let reentered = reenter.clone();
+
+    Light::Green.connect_blinked(Box::new(RefCell::new(move || {
+        if reentered.get() {
+            Light::Green.emit_blinked()
+        }
+    })));
+
+    let payload = catch_unwind(|| Light::Green.emit_blinked()).unwrap_err();
+
+    assert_eq!(
+        payload.downcast_ref::<String>().map(String::as_str),
+        Some(MESSAGE.replace("switched", "blinked").as_str()),
+    );
+
+    reenter.set(false);
+    Light::Green.emit_blinked();
+
+    assert!(take_panics().is_empty());
+}
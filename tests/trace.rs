@@ -0,0 +1,92 @@
+// Copyright 2018 Sergey Sherkunov <leinlawun@leinlawun.org>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(plugin)]
+#![plugin(sig)]
+#![sig]
+
+extern crate sig_runtime;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use sig_runtime::{clear_tracer, set_tracer, Emission};
+
+type Trace = Rc<RefCell<Vec<(&'static str, &'static str, usize, usize)>>>;
+
+#[derive(Default)]
+struct Counter;
+
+impl Counter {
+    sig! {
+        fn changed(&self, value: i32);
+    }
+}
+
+trait Widget {
+    #[sig]
+    fn clicked(&self);
+}
+
+#[derive(Default)]
+struct Button;
+
+impl Widget for Button {}
+
+fn tracer() -> Trace {
+    let trace = Rc::new(RefCell::new(vec![]));
+    let traced = trace.clone();
+
+    set_tracer(move |emission: &Emission, index, _| {
+        traced.borrow_mut().push((
+            emission.signal(),
+            emission.sender(),
+            emission.slots(),
+            index,
+        ))
+    });
+
+    trace
+}
+
+#[test]
+fn signal() {
+    let trace = tracer();
+    let mut counter = Counter::default();
+
+    counter.connect_changed(Box::new(|_| {}));
+    counter.connect_changed(Box::new(|_| {}));
+    counter.emit_changed(1);
+    clear_tracer();
+    counter.emit_changed(2);
+
+    assert_eq!(
+        *trace.borrow(),
+        [("changed", "Counter", 2, 0), ("changed", "Counter", 2, 1)],
+    );
+}
+
+#[test]
+fn trait_signal() {
+    let trace = tracer();
+    let mut button: Box<Widget> = Box::new(Button::default());
+
+    button.connect_clicked(Box::new(|| {}));
+    button.connect_clicked(Box::new(|| {}));
+    button.connect_clicked(Box::new(|| {}));
+    button.clicked();
+
+    assert_eq!(
+        *trace.borrow(),
+        [
+            ("clicked", "Button", 3, 0),
+            ("clicked", "Button", 3, 1),
+            ("clicked", "Button", 3, 2),
+        ],
+    );
+}
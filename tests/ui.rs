@@ -0,0 +1,24 @@
+// Copyright 2018 Sergey Sherkunov <leinlawun@leinlawun.org>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+extern crate compiletest_rs as compiletest;
+
+use std::path::PathBuf;
+
+#[test]
+fn ui() {
+    let mut config = compiletest::Config::default();
+
+    config.mode = "ui".parse().unwrap();
+    config.src_base = PathBuf::from("tests/ui");
+    config.target_rustcflags =
+        Some("-L target/debug -L target/debug/deps".to_owned());
+    config.clean_rmeta();
+
+    compiletest::run_tests(&config);
+}
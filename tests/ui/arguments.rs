@@ -0,0 +1,27 @@
+// Copyright 2018 Sergey Sherkunov <leinlawun@leinlawun.org>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(plugin)]
+#![plugin(sig)]
+#![sig]
+
+extern crate sig_runtime;
+
+struct Person {
+    #[sig(notify)]
+    first: String,
+}
+
+impl Person {
+    #[sig(computed(first))]
+    fn greeting(&self, greeting: &str) -> String {
+        format!("{}, {}", greeting, self.first)
+    }
+}
+
+fn main() {}
@@ -0,0 +1,52 @@
+// Copyright 2018 Sergey Sherkunov <leinlawun@leinlawun.org>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(plugin)]
+#![plugin(sig)]
+#![sig]
+
+extern crate sig_runtime;
+
+mod signals {
+    pub trait Display {
+        #[sig]
+        fn shown(&self);
+    }
+
+    pub trait Widget {
+        #[sig]
+        fn clicked(&self);
+    }
+}
+
+mod widgets {
+    use std::fmt::{self, Display};
+
+    use signals::Widget as Base;
+
+    #[derive(Default)]
+    pub struct Label;
+
+    impl Display for Label {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "label")
+        }
+    }
+
+    impl Base for Label {}
+}
+
+fn main() {
+    use signals::Widget;
+
+    let mut label = widgets::Label::default();
+
+    label.connect_clicked(Box::new(|| println!("{}", "clicked")));
+    label.clicked();
+    println!("{}", label);
+}
@@ -0,0 +1,55 @@
+// Copyright 2018 Sergey Sherkunov <leinlawun@leinlawun.org>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(plugin)]
+#![plugin(sig)]
+#![sig]
+#![deny(warnings)]
+
+extern crate sig_runtime;
+
+pub struct Person {
+    #[sig(notify)]
+    name: String,
+}
+
+impl Person {
+    sig! {
+        fn waved(&self);
+    }
+
+    #[sig(computed(name))]
+    fn greeting(&self) -> String {
+        format!("Hello, {}!", self.name)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum Light {
+    Red,
+    Green,
+}
+
+impl Light {
+    sig! {
+        fn switched(&self);
+    }
+}
+
+trait Widget {
+    #[sig]
+    fn clicked(&self);
+}
+
+struct Button;
+
+impl Widget for Button {}
+
+fn main() {
+    let _ = (Light::Red, Light::Green);
+}